@@ -14,12 +14,15 @@ downloading-failed = Downloading failed
 unpacking-failed = Unpacking failed
 
 game-file-repairing-error = Failed to repair game file
+game-files-repairing-failed = Failed to repair {$count} game files
 integrity-files-getting-error = Failed to get integrity files
 
 background-downloading-failed = Failed to download background picture
 components-index-sync-failed = Failed to sync components index
 components-index-verify-failed = Failed to verify components index
 config-update-error = Failed to save config
+config-reading-error = Failed to read config
+settings-reading-error = Failed to read launcher settings
 wine-prefix-update-failed = Failed to update wine prefix
 dxvk-install-failed = Failed to install DXVK
 voice-package-deletion-error = Failed to delete voice package
@@ -58,3 +61,22 @@ game-session-apply-failed = Failed to apply game session
 
 discord-rpc-icons-fetch-failed = Failed to fetch Discord RPC icons
 discord-rpc-icon-download-failed = Failed to download Discord RPC icon
+
+# Offline installation
+
+archive-opening-error = Failed to open game archive
+archive-not-needed = Game is already updated
+archive-version-mismatch = Archive doesn't match the required game version
+archive-expected-name = Expected {$expected}, got {$actual}
+archive-expected-size = Expected archive size is {$expected}, got {$actual}
+archive-no-game-version = Launcher doesn't know which game version is required, so the archive can't be verified
+archive-no-cached-version = Run the launcher with network access once to find out which archive is required
+archive-installed-version-mismatch = Archive was unpacked, but the game version wasn't updated
+archive-installed-versions = Expected {$expected}, installed {$installed}. Try to repair the game or install it online
+integrity-report-exporting-error = Failed to export integrity report
+excluded-files-modified = Some files excluded from the repair differ from the official ones
+manifest-reading-error = Failed to read integrity manifest
+manifest-saving-error = Failed to save integrity manifest
+manifest-export-offline = Integrity manifest can't be exported in offline mode
+manifest-version-mismatch = Integrity manifest was exported for another game version
+manifest-versions = Manifest version is {$manifest}, installed version is {$installed}
@@ -24,6 +24,19 @@ chinese = Chinese
 migrate-installation = Migrate installation
 migrate-installation-description = Open special window where you can change your game installation folder
 repair-game = Repair game
+verify-game = Verify game files
+verify-game-description = Check game files and show broken ones without repairing them
+
+game-repair = Game repair
+force-full-verify = Force full verification
+force-full-verify-description = Re-hash all the game files. Otherwise only files changed since the last verification are checked
+verify-after-update = Verify after update
+verify-after-update-description = Verify game files changed by the installed update and repair broken ones
+repair-exclusions = Repair exclusions
+repair-exclusions-description = Game files matching these patterns are never repaired. You're warned if they differ from the official ones
+new-exclusion = New exclusion
+new-exclusion-description = Path relative to the game folder. Use * for any characters within a folder, ** for any folders and ? for a single character. Patterns without / match file names in all the folders, and patterns starting with / match files from the game folder only
+exclusion-pattern = Pattern
 
 status = Status
 
@@ -33,6 +46,14 @@ game-not-installed = not installed
 game-predownload-available = Game update pre-downloading available: {$old} -> {$new}
 game-update-available = Game update available: {$old} -> {$new}
 game-outdated = Game is too outdated and can't be updated. Latest version: {$latest}
+game-updated = Game was updated
+game-updated-description = Game was automatically updated from {$old} to {$new}
+
+auto-update = Automatic updates
+auto-update-description = Download and install game updates in background while the launcher is opened
+auto-update-interval = Check interval, hours
+auto-update-at-hour = Check daily at hour (0-23)
+auto-update-at-hour-description = Leave empty to use check interval
 
 player-patch-version = Player patch version
 player-patch-version-description = Main patch that lets you play the game on Linux
@@ -13,6 +13,7 @@ launcher-folder = Launcher folder
 game-folder = Game folder
 config-file = Config file
 debug-file = Debug file
+install-from-archive = Install from archive
 wish-url = Open wishes
 about = About
 
@@ -21,6 +22,8 @@ close = Close
 save = Save
 continue = Continue
 resume = Resume
+pause = Pause
+cancel = Cancel
 exit = Exit
 check = Check
 restart = Restart
@@ -36,12 +39,64 @@ loading-launcher-state = Loading launcher state
 loading-launcher-state--components = Loading launcher state: checking components
 loading-launcher-state--game = Loading launcher state: verifying game version
 
+skip = Skip
+startup-task-network = Checking network connection
+startup-task-background = Downloading background picture
+startup-task-components = Updating components index
+startup-task-game-version = Checking game version
+startup-task-timed-out = {$task} took too long and was skipped
+
 
 checking-free-space = Checking free space
 downloading = Downloading
 unpacking = Unpacking
+paused = Paused
+cancelling = Cancelling
+time-left = {$time} left
+retrying-download = Retrying download ({$attempt}/{$attempts})
+downloading-from-mirror = Downloading from {$mirror}
+
+setup-checklist = Setup checklist
+setup-checklist-description = Everything needed to run the game. Failed checks can be fixed separately
+fix-everything = Fix everything
+download-game = Download game
+setup-step = Step {$number}: {$step}
+setup-step-failed = Setup step failed: {$step}
+setup-step-not-fixed = Step was finished but launcher still requires it
+setup-finished = Everything is ready to run the game
+wine-prefix = Wine prefix
+fonts = Fonts
+check-installed = Installed
+check-not-installed = Not installed
+check-not-downloaded = {$name} is selected but not downloaded
+check-wine-not-selected = Wine version is not selected
+check-prefix-not-exists = Prefix is not created
+check-requires-wine = Requires wine to be downloaded
+check-requires-prefix = Requires wine prefix to be created
+check-font-not-installed = Font is not installed
+check-dxvk-not-used = Not used
+check-offline = Game version can't be checked in offline mode
+check-game-checking = Checking game version...
+check-game-latest = Version {$version} is installed
+check-game-outdated = Version {$current} is installed, {$latest} is available
+check-game-not-installed = Game is not installed
+
+game-archive = Game archive
+launcher-busy = Launcher is busy. Wait until the current action finishes
+
+download-preview = Download preview
+download-size = Download size
+unpacked-size = Unpacked size
+game-free-space = Free space in game folder
+temp-free-space = Free space in temp folder
+estimated-time = Estimated time
+not-enough-space = Not enough free space to download and unpack the game
+change-temp-folder = Change temp folder
 verifying-files = Verifying files
 repairing-files = Repairing files
+repair-cancelled = Game repair was cancelled
+update-verified = Updated game files passed verification
+update-files-repaired = {$count} game files were broken by the update and repaired
 migrating-folders = Migrating folders
 applying-hdiff = Applying hdiff patches
 removing-outdated = Removing outdated files
@@ -69,3 +124,25 @@ main-window--version-outdated-tooltip = Version is too outdated and can't be upd
 preferences = Preferences
 general = General
 enhancements = Enhancements
+
+offline-mode = Offline mode
+offline-mode-description = Network is unavailable. Cached background and components index and locally installed game version are used. Restart the launcher to check for updates
+
+integrity-report = Integrity report
+integrity-report-clean = All the game files passed verification
+integrity-report-broken = {$count} game files are broken. Select files which should be repaired
+integrity-file-sizes = Expected {$expected}, found {$actual}
+integrity-more-files = And {$count} more files. Export the report to see all of them
+mismatched-files = Mismatched files
+mismatched-files-description = These files exist but differ from the official ones
+missing-files = Missing files
+missing-files-description = These files should exist in the game folder
+extra-files = Extra files
+extra-files-description = These files are not listed in the official files list. They're not modified by the repair
+excluded-files = Modified excluded files
+excluded-files-description = These files differ from the official ones, but they're excluded from the repair in preferences
+repair-selected = Repair selected
+export-report = Export report
+export-integrity-manifest = Export integrity manifest
+verify-with-manifest = Verify using manifest
+manifest-exported = Exported {$files} game files to the integrity manifest
@@ -0,0 +1,39 @@
+network = Network
+
+speed-limit = Speed limit
+speed-limit-description = Limit download speed of the game, components and other launcher files
+limit-download-speed = Limit download speed
+speed-limit-kibs = Speed limit, KiB/s
+speed-limit-schedule = Unlimited at scheduled time
+speed-limit-schedule-description = Don't limit download speed in the given hours range, e.g. at night
+unlimited-from = Unlimited from hour (0-23)
+unlimited-to = Unlimited to hour (0-23)
+
+game-downloads = Game downloads
+download-retries = Download attempts for each source
+mirrors = Mirrors
+mirrors-settings-description = Alternative servers used when the official CDN fails
+mirrors-description = Folders with the game archives served over HTTP. Local server can be used as well, e.g. python -m http.server
+new-mirror = New mirror
+new-mirror-description = Mirrors are used in the order they were added
+mirror-url = Mirror URL
+measure-latency = Measure
+latency-not-measured = Latency not measured
+measuring-latency = Measuring latency...
+mirror-latency = Latency: {$ms} ms
+mirror-unavailable = Unavailable
+
+proxy = Proxy
+proxy-description = Proxy used for the launcher network traffic: game and components downloads, components index sync and other launcher requests. The game itself is run without it
+test-connection = Test connection
+connection-succeeded = Connection succeeded ({$ms} ms)
+connection-failed = Connection failed
+use-proxy = Use proxy
+proxy-restart-required = Restart the launcher to apply changed proxy settings
+proxy-type = Proxy type
+proxy-host = Host
+proxy-port = Port
+proxy-username = Username (optional)
+proxy-password = Password (optional)
+ca-bundle = Certificate authorities bundle
+ca-bundle-system = System certificates
@@ -0,0 +1,148 @@
+//! Compare game files verification scheduling strategies on a synthetic files tree
+//!
+//! Run with `cargo bench --bench verify`
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use std::io::Read;
+
+#[path = "../src/actions/scheduler.rs"]
+mod scheduler;
+
+const THREADS: usize = 4;
+
+/// Synthetic file: path and size
+#[derive(Debug, Clone)]
+struct File {
+    path: PathBuf,
+    size: u64
+}
+
+/// Read file and calculate its simple checksum, like the verifier does
+fn verify(file: &File) -> bool {
+    let mut reader = std::fs::File::open(&file.path).unwrap();
+    let mut buf = vec![0; 64 * 1024];
+
+    let mut hash: u64 = 0xcbf29ce484222325;
+
+    loop {
+        let read = reader.read(&mut buf).unwrap();
+
+        if read == 0 {
+            break;
+        }
+
+        for byte in &buf[..read] {
+            hash = (hash ^ *byte as u64).wrapping_mul(0x100000001b3);
+        }
+    }
+
+    hash != 0
+}
+
+/// Files tree similar to the game one: a few huge archives followed by lots of small files
+fn create_tree(root: &Path) -> Vec<File> {
+    let mut sizes = vec![64 * 1024 * 1024; 3];
+
+    sizes.extend(std::iter::repeat(256 * 1024).take(400));
+
+    sizes.into_iter()
+        .enumerate()
+        .map(|(i, size)| {
+            let path = root.join(format!("file-{i}"));
+
+            let content = (0..size).map(|j| (i + j as usize) as u8).collect::<Vec<_>>();
+
+            std::fs::write(&path, content).unwrap();
+
+            File { path, size }
+        })
+        .collect()
+}
+
+/// Previous strategy: files are split into static chunks of about the same total size
+fn static_chunks(files: &[File]) -> usize {
+    let total = files.iter().map(|file| file.size).sum::<u64>();
+    let median_size = total / THREADS as u64;
+
+    let mut i = 0;
+
+    let (sender, receiver) = std::sync::mpsc::channel();
+
+    for _ in 0..THREADS {
+        let mut thread_files = Vec::new();
+        let mut thread_files_size = 0;
+
+        while i < files.len() {
+            thread_files.push(files[i].clone());
+
+            thread_files_size += files[i].size;
+            i += 1;
+
+            if thread_files_size >= median_size {
+                break;
+            }
+        }
+
+        let sender = sender.clone();
+
+        std::thread::spawn(move || {
+            for file in thread_files {
+                sender.send(verify(&file)).unwrap();
+            }
+        });
+    }
+
+    drop(sender);
+
+    receiver.iter().filter(|status| *status).count()
+}
+
+/// Current strategy: shared queue, largest files first
+fn largest_first(files: &[File]) -> usize {
+    let mut verified = 0;
+
+    scheduler::largest_first(files.to_vec(), THREADS, |file| file.size, verify, |_, status| {
+        if status {
+            verified += 1;
+        }
+    });
+
+    verified
+}
+
+fn measure(name: &str, files: &[File], strategy: fn(&[File]) -> usize) -> Duration {
+    // Warm up page cache so both strategies read files from memory
+    strategy(files);
+
+    let mut best = Duration::MAX;
+
+    for _ in 0..5 {
+        let started = Instant::now();
+
+        assert_eq!(strategy(files), files.len());
+
+        best = best.min(started.elapsed());
+    }
+
+    println!("{name:>14}: {best:?}");
+
+    best
+}
+
+fn main() {
+    let root = std::env::temp_dir().join(format!("anime-borb-launcher-verify-bench-{}", std::process::id()));
+
+    std::fs::create_dir_all(&root).unwrap();
+
+    let files = create_tree(&root);
+
+    println!("Verifying {} files using {THREADS} threads", files.len());
+
+    let old = measure("static chunks", &files, static_chunks);
+    let new = measure("largest first", &files, largest_first);
+
+    println!("       speedup: {:.2}x", old.as_secs_f64() / new.as_secs_f64());
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
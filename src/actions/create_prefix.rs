@@ -0,0 +1,42 @@
+use anime_launcher_sdk::wincompatlib::prelude::*;
+
+use crate::*;
+
+use super::*;
+
+/// Create wine prefix using selected wine version
+pub fn create_prefix(updater: impl Fn(ActionUpdate)) -> Result<(), ActionError> {
+    let config = Config::get().map_err(|err| ActionError::from_err("config-reading-error", err))?;
+
+    let wine = match config.get_selected_wine() {
+        Ok(Some(wine)) => wine,
+
+        Ok(None) => {
+            tracing::error!("Failed to get selected wine executable");
+
+            return Err(ActionError::new(tr("failed-get-selected-wine"), None));
+        }
+
+        Err(err) => {
+            tracing::error!("Failed to get selected wine executable: {err}");
+
+            return Err(ActionError::from_err("failed-get-selected-wine", err));
+        }
+    };
+
+    updater(ActionUpdate::Stage(ActionStage::CreatingPrefix));
+
+    let wine = wine
+        .to_wine(config.components.path, Some(config.game.wine.builds.join(&wine.name)))
+        .with_prefix(&config.game.wine.prefix)
+        .with_loader(WineLoader::Current)
+        .with_arch(WineArch::Win64);
+
+    if let Err(err) = wine.init_prefix(None::<&str>) {
+        tracing::error!("Failed to create wine prefix: {err}");
+
+        return Err(ActionError::from_err("wine-prefix-update-failed", err));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,282 @@
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anime_launcher_sdk::anime_game_core::installer::free_space;
+
+use crate::*;
+use crate::settings::Settings;
+use crate::network::mirrors::{mirror_url, backoff};
+
+use super::*;
+use super::install_archive::verify_installed_version;
+
+/// Download and install game version diff
+///
+/// Failed downloads are retried with exponential backoff, then configured mirrors are used.
+/// Download can be paused or cancelled using the given handle
+pub fn download_diff(diff: VersionDiff, handle: &DownloadHandle, updater: impl Fn(ActionUpdate) + Clone + Send + 'static) -> Result<DownloadStatus, ActionError> {
+    let config = Config::get().map_err(|err| ActionError::from_err("config-reading-error", err))?;
+    let settings = Settings::get().unwrap_or_default();
+
+    let temp = config.launcher.temp.unwrap_or_else(std::env::temp_dir);
+    let file_name = diff.file_name();
+
+    let partial_file = file_name.as_ref()
+        .map(|name| temp.join(name))
+        .unwrap_or_default();
+
+    // Temp folder could be changed after the diff was found
+    let diff = diff.with_temp_folder(temp);
+
+    // Errors of single attempts are only logged. Caller will report the last one
+    let attempt_updater = {
+        let updater = updater.clone();
+
+        move |update| match update {
+            ActionUpdate::Error(err) => tracing::warn!("Download attempt failed: {err}"),
+            update => updater(update)
+        }
+    };
+
+    // Remember hashes of verified game files to find files changed by the update.
+    // Cache file itself is invalidated once the update is installed
+    let previous_cache = match diff {
+        VersionDiff::Outdated { .. } if settings.repair.verify_after_update => Some(IntegrityCache::load(&config.game.path)),
+        _ => None
+    };
+
+    // `None` is the official CDN
+    let mut sources = vec![None];
+
+    if let Some(file_name) = &file_name {
+        sources.extend(settings.network.mirrors.iter().map(|base| Some(mirror_url(base, file_name))));
+    }
+
+    let attempts = settings.network.retries.max(1);
+    let mut last_error = None;
+
+    for source in sources {
+        for attempt in 0..attempts {
+            if attempt > 0 {
+                updater(ActionUpdate::Stage(ActionStage::RetryingDownload {
+                    attempt: attempt + 1,
+                    attempts
+                }));
+
+                if let Some(status) = handle.sleep(backoff(attempt - 1)) {
+                    handle.cleanup(&partial_file);
+
+                    return Ok(status);
+                }
+            }
+
+            let result = match &source {
+                None => download_from_cdn(&diff, &config.game.path, &partial_file, handle, attempt_updater.clone()),
+                Some(url) => download_from_mirror(url, &diff, &config.game.path, &partial_file, handle, attempt_updater.clone())
+            };
+
+            match result {
+                Ok(status) => {
+                    // Installed files don't match the cached ones anymore
+                    if status == DownloadStatus::Finished {
+                        IntegrityCache::invalidate();
+
+                        // Update is already installed, so verification errors don't fail the download
+                        if let Some(previous_cache) = &previous_cache {
+                            if let Err(err) = verify_update(previous_cache, handle, &updater) {
+                                tracing::error!("Failed to verify updated files: {err}");
+
+                                updater(ActionUpdate::Error(err));
+                            }
+                        }
+                    }
+
+                    return Ok(status);
+                }
+
+                Err(err) => {
+                    tracing::error!("Downloading failed (attempt {} of {attempts}): {err}", attempt + 1);
+
+                    last_error = Some(err);
+                }
+            }
+        }
+    }
+
+    Err(last_error.unwrap_or_else(|| ActionError::new(tr("downloading-failed"), None)))
+}
+
+fn download_from_cdn(
+    diff: &VersionDiff,
+    game_path: &Path,
+    partial_file: &Path,
+    handle: &DownloadHandle,
+    updater: impl Fn(ActionUpdate) + Clone + Send + 'static
+) -> Result<DownloadStatus, ActionError> {
+    let (installer_updater, failed) = installer_updater(updater, "downloading-failed", "unpacking-failed");
+
+    let Some(url) = diff_url(diff) else {
+        return Err(ActionError::new(tr("archive-not-needed"), None));
+    };
+
+    // Archive is downloaded by the launcher so it can be interrupted between chunks.
+    // Installer finds the completely downloaded archive and only unpacks it
+    match handle.download(url, partial_file, installer_updater.clone()) {
+        Ok(DownloadStatus::Finished) => (),
+        Ok(status) => return Ok(status),
+
+        Err(err) => return Err(ActionError::from_err("downloading-failed", err))
+    }
+
+    if let Err(err) = diff.install_to(game_path, installer_updater) {
+        return Err(ActionError::from_err("downloading-failed", err));
+    }
+
+    if failed.load(Ordering::Relaxed) {
+        return Err(ActionError::new(tr("downloading-failed"), None));
+    }
+
+    Ok(DownloadStatus::Finished)
+}
+
+/// Download diff archive from the mirror and install it using the SDK installer
+fn download_from_mirror(
+    url: &str,
+    diff: &VersionDiff,
+    game_path: &Path,
+    archive: &Path,
+    handle: &DownloadHandle,
+    updater: impl Fn(ActionUpdate) + Clone + Send + 'static
+) -> Result<DownloadStatus, ActionError> {
+    tracing::info!("Downloading game from mirror: {url}");
+
+    let expected = ExpectedArchive::from_diff(diff)
+        .ok_or_else(|| ActionError::new(tr("archive-not-needed"), None))?;
+
+    let host = url.split('/').nth(2).unwrap_or(url).to_string();
+
+    // Installer sets its own caption when downloading is started,
+    // so the mirror is shown right after it
+    let mirror_updater = move |update: ActionUpdate| {
+        let started = matches!(update, ActionUpdate::Installer(InstallerUpdate::DownloadingStarted(_)));
+
+        updater(update);
+
+        if started {
+            updater(ActionUpdate::Stage(ActionStage::DownloadingFromMirror(host.clone())));
+        }
+    };
+
+    let (installer_updater, failed) = installer_updater(mirror_updater, "downloading-failed", "unpacking-failed");
+
+    let mut installer = Installer::new(url)
+        .map_err(|err| ActionError::from_err("downloading-failed", err))?
+        .with_temp_folder(archive.parent().map(Path::to_path_buf).unwrap_or_else(std::env::temp_dir));
+
+    if let Some(file_name) = archive.file_name() {
+        installer = installer.with_filename(file_name.to_string_lossy().to_string());
+    }
+
+    match handle.download(url, archive, installer_updater.clone()) {
+        Ok(DownloadStatus::Finished) => (),
+        Ok(status) => return Ok(status),
+
+        Err(err) => return Err(ActionError::from_err("downloading-failed", err))
+    }
+
+    installer.install(game_path.to_path_buf(), installer_updater);
+
+    if failed.load(Ordering::Relaxed) {
+        return Err(ActionError::new(tr("downloading-failed"), None));
+    }
+
+    if archive.exists() {
+        if let Err(err) = std::fs::remove_file(archive) {
+            tracing::warn!("Failed to remove downloaded archive: {err}");
+        }
+    }
+
+    // Mirrors serve the same archives, but they're installed without the version diff
+    verify_installed_version(&expected)?;
+
+    Ok(DownloadStatus::Finished)
+}
+
+/// Get url of the diff archive on the official CDN
+fn diff_url(diff: &VersionDiff) -> Option<&str> {
+    match diff {
+        VersionDiff::Latest(_) => None,
+
+        VersionDiff::Outdated { url, .. } |
+        VersionDiff::NotInstalled { url, .. } => Some(url)
+    }
+}
+
+/// Download size and disk space estimation for a game version diff
+#[derive(Debug, Clone)]
+pub struct DownloadPreview {
+    pub download_size: Option<u64>,
+    pub unpacked_size: Option<u64>,
+
+    pub game_path: PathBuf,
+    pub game_free_space: Option<u64>,
+
+    pub temp_path: PathBuf,
+    pub temp_free_space: Option<u64>,
+
+    /// Estimated downloading time based on the recent download speed
+    pub eta: Option<Duration>
+}
+
+impl DownloadPreview {
+    /// Check if there's enough free space to download and unpack the diff.
+    /// Returns `true` if the space can't be checked
+    pub fn enough_space(&self) -> bool {
+        let same_disk = free_space::is_same_disk(existing_parent(&self.game_path), existing_parent(&self.temp_path));
+
+        match (self.download_size, self.unpacked_size, self.game_free_space, self.temp_free_space) {
+            (Some(download), Some(unpacked), Some(game_free), _) if same_disk => game_free >= download + unpacked,
+
+            (Some(download), Some(unpacked), Some(game_free), Some(temp_free)) => {
+                temp_free >= download && game_free >= unpacked
+            }
+
+            _ => true
+        }
+    }
+}
+
+/// Find existing path's parent to get free space of the filesystem
+/// where the path will be created
+fn existing_parent(path: &Path) -> &Path {
+    path.ancestors()
+        .find(|path| path.exists())
+        .unwrap_or(path)
+}
+
+/// Get download size and disk space estimation for the game version diff
+pub fn download_preview(diff: &VersionDiff) -> Result<DownloadPreview, ActionError> {
+    let config = Config::get().map_err(|err| ActionError::from_err("config-reading-error", err))?;
+
+    let temp_path = config.launcher.temp.unwrap_or_else(std::env::temp_dir);
+
+    let download_size = diff.downloaded_size();
+
+    let eta = match (download_size, crate::network::speed::recent_speed()) {
+        (Some(size), Some(speed)) if speed > 0 => Some(Duration::from_secs(size / speed)),
+        _ => None
+    };
+
+    Ok(DownloadPreview {
+        download_size,
+        unpacked_size: diff.unpacked_size(),
+
+        game_free_space: free_space::available(existing_parent(&config.game.path)),
+        game_path: config.game.path,
+
+        temp_free_space: free_space::available(existing_parent(&temp_path)),
+        temp_path,
+
+        eta
+    })
+}
@@ -0,0 +1,74 @@
+use anime_launcher_sdk::components::wine;
+
+use crate::*;
+
+use super::*;
+
+/// Select already downloaded wine version or download the latest one
+///
+/// Download can be paused or cancelled using the given handle
+pub fn download_wine(handle: &DownloadHandle, updater: impl Fn(ActionUpdate) + Clone + Send + 'static) -> Result<DownloadStatus, ActionError> {
+    let mut config = Config::get().map_err(|err| ActionError::from_err("config-reading-error", err))?;
+
+    let downloaded = wine::get_downloaded(&config.components.path, &config.game.wine.builds)
+        .map_err(|err| ActionError::from_err("downloaded-wine-list-failed", err))?;
+
+    // Select downloaded version
+    if !downloaded.is_empty() {
+        config.game.wine.selected = Some(downloaded[0].versions[0].name.clone());
+
+        Config::update(config);
+
+        return Ok(DownloadStatus::Finished);
+    }
+
+    // Or download new one if none is available
+    let latest = wine::Version::latest(&config.components.path)
+        .map_err(|err| ActionError::from_err("wine-install-failed", err))?;
+
+    // Choose selected wine version or use latest available one
+    let wine = match &config.game.wine.selected {
+        Some(version) => match wine::Version::find_in(&config.components.path, version) {
+            Ok(Some(version)) => version,
+            _ => latest
+        }
+
+        None => latest
+    };
+
+    tracing::info!("Installing wine: {}", wine.name);
+
+    // Download wine version
+    let mut installer = Installer::new(&wine.uri)
+        .map_err(|err| ActionError::from_err("wine-install-failed", err))?;
+
+    if let Some(temp_folder) = &config.launcher.temp {
+        installer.temp_folder = temp_folder.to_path_buf();
+    }
+
+    let (installer_updater, failed) = installer_updater(updater, "downloading-failed", "unpacking-failed");
+
+    let partial_file = installer.temp_folder.join(installer.downloader.get_filename());
+
+    // Archive is downloaded by the launcher so it can be interrupted between chunks.
+    // Installer finds the completely downloaded archive and only unpacks it
+    match handle.download(&wine.uri, &partial_file, installer_updater.clone()) {
+        Ok(DownloadStatus::Finished) => (),
+        Ok(status) => return Ok(status),
+
+        Err(err) => return Err(ActionError::from_err("downloading-failed", err))
+    }
+
+    installer.install(&config.game.wine.builds, installer_updater);
+
+    // Errors were already reported by the installer
+    if failed.load(Ordering::Relaxed) {
+        return Err(ActionError::new(tr("wine-install-failed"), None));
+    }
+
+    config.game.wine.selected = Some(wine.name);
+
+    Config::update(config);
+
+    Ok(DownloadStatus::Finished)
+}
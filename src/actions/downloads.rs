@@ -0,0 +1,479 @@
+use std::collections::VecDeque;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::time::{Duration, Instant};
+
+use anime_launcher_sdk::anime_game_core::prelude::*;
+use anime_launcher_sdk::anime_game_core::minreq;
+
+use super::ActionError;
+
+/// Amount of bytes downloaded between interruption checks
+const DOWNLOAD_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Result of the download job
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DownloadStatus {
+    Finished,
+
+    /// Download was paused and its partial file was kept in the temp folder
+    Paused,
+
+    /// Download was cancelled and its partial file was removed
+    Cancelled
+}
+
+/// Handle used to interrupt running download
+///
+/// Downloads can be interrupted only while they're downloading files.
+/// Unpacking is never interrupted so game files can't be left in broken state
+#[derive(Debug, Clone, Default)]
+pub struct DownloadHandle(Arc<AtomicU8>);
+
+impl DownloadHandle {
+    const RUNNING: u8 = 0;
+    const PAUSED: u8 = 1;
+    const CANCELLED: u8 = 2;
+
+    #[inline]
+    pub fn pause(&self) {
+        self.0.store(Self::PAUSED, Ordering::Relaxed);
+    }
+
+    #[inline]
+    pub fn cancel(&self) {
+        self.0.store(Self::CANCELLED, Ordering::Relaxed);
+    }
+
+    #[inline]
+    fn reset(&self) {
+        self.0.store(Self::RUNNING, Ordering::Relaxed);
+    }
+
+    /// Get requested interruption, if any
+    pub fn interruption(&self) -> Option<DownloadStatus> {
+        match self.0.load(Ordering::Relaxed) {
+            Self::PAUSED    => Some(DownloadStatus::Paused),
+            Self::CANCELLED => Some(DownloadStatus::Cancelled),
+
+            _ => None
+        }
+    }
+
+    /// Block current thread for given time or until interruption was requested
+    pub(crate) fn sleep(&self, duration: Duration) -> Option<DownloadStatus> {
+        let started = Instant::now();
+
+        while started.elapsed() < duration {
+            if let Some(status) = self.interruption() {
+                return Some(status);
+            }
+
+            std::thread::sleep(Duration::from_millis(100));
+        }
+
+        self.interruption()
+    }
+
+    /// Download file from the given url, continuing its already downloaded part
+    ///
+    /// Interruption is checked after every downloaded chunk, so the download is stopped
+    /// by returning from this function and no SDK code is interrupted midway.
+    /// Returns `DownloadStatus::Finished` once the whole file was downloaded
+    pub(crate) fn download(&self, url: &str, file: &Path, updater: impl Fn(InstallerUpdate)) -> anyhow::Result<DownloadStatus> {
+        // Download was interrupted before it was started (e.g. it was cancelled while paused)
+        if let Some(status) = self.interrupt(file) {
+            return Ok(status);
+        }
+
+        let mut downloaded = file.metadata().map(|metadata| metadata.len()).unwrap_or(0);
+
+        let mut request = minreq::get(url);
+
+        if downloaded > 0 {
+            tracing::info!("Continuing download from {downloaded} bytes: {url}");
+
+            request = request.with_header("Range", format!("bytes={downloaded}-"));
+        }
+
+        let response = request.send_lazy()?;
+
+        let continued = match response.status_code {
+            206 => true,
+
+            // Partial file is already completely downloaded
+            416 if downloaded > 0 => {
+                updater(InstallerUpdate::DownloadingFinished);
+
+                return Ok(DownloadStatus::Finished);
+            }
+
+            // Server doesn't support ranges so the file is downloaded from the beginning
+            200..=299 => {
+                downloaded = 0;
+
+                false
+            }
+
+            code => anyhow::bail!("Server responded with {code} status code: {}", response.reason_phrase)
+        };
+
+        let total = response.headers.get("content-length")
+            .and_then(|length| length.parse::<u64>().ok())
+            .map(|length| length + downloaded)
+            .unwrap_or_default();
+
+        if let Some(parent) = file.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let mut options = OpenOptions::new();
+
+        if continued {
+            options.append(true);
+        } else {
+            options.write(true).truncate(true);
+        }
+
+        let mut output = options.create(true).open(file)?;
+
+        updater(InstallerUpdate::DownloadingStarted(file.to_path_buf()));
+
+        let mut chunk = Vec::with_capacity(DOWNLOAD_CHUNK_SIZE);
+        let mut response = response.peekable();
+
+        while response.peek().is_some() {
+            for byte in response.by_ref().take(DOWNLOAD_CHUNK_SIZE) {
+                chunk.push(byte?.0);
+            }
+
+            output.write_all(&chunk)?;
+
+            downloaded += chunk.len() as u64;
+
+            chunk.clear();
+
+            updater(InstallerUpdate::DownloadingProgress(downloaded, total));
+
+            if let Some(status) = self.interrupt(file) {
+                return Ok(status);
+            }
+        }
+
+        output.flush()?;
+
+        updater(InstallerUpdate::DownloadingFinished);
+
+        Ok(DownloadStatus::Finished)
+    }
+
+    /// Get requested interruption and clean up partial download file for it
+    fn interrupt(&self, partial_file: &Path) -> Option<DownloadStatus> {
+        let status = self.interruption()?;
+
+        self.cleanup(partial_file);
+
+        tracing::info!("Download interrupted: {status:?}");
+
+        Some(status)
+    }
+
+    /// Remove partial download file if download was cancelled
+    ///
+    /// Paused downloads keep their partial files to continue them later
+    pub(crate) fn cleanup(&self, partial_file: &Path) {
+        if self.interruption() == Some(DownloadStatus::Cancelled) && partial_file.exists() {
+            tracing::info!("Removing partial download file: {:?}", partial_file);
+
+            if let Err(err) = std::fs::remove_file(partial_file) {
+                tracing::error!("Failed to remove partial download file: {err}");
+            }
+        }
+    }
+}
+
+type DownloadJob = Box<dyn FnMut(&DownloadHandle) -> Result<DownloadStatus, ActionError> + Send>;
+type DownloadCallback = Box<dyn FnOnce(Result<DownloadStatus, ActionError>) + Send>;
+
+enum QueueMsg {
+    Push(DownloadJob, DownloadCallback),
+    Resume,
+    Cancel
+}
+
+/// Queue which runs download jobs one after another in a background thread
+///
+/// Paused job stays at the head of the queue until it's resumed or cancelled
+pub struct DownloadQueue {
+    sender: Sender<QueueMsg>,
+    handle: DownloadHandle
+}
+
+impl DownloadQueue {
+    pub fn new() -> Self {
+        let (sender, receiver) = mpsc::channel();
+        let handle = DownloadHandle::default();
+
+        std::thread::spawn({
+            let handle = handle.clone();
+
+            move || Self::worker(receiver, handle)
+        });
+
+        Self {
+            sender,
+            handle
+        }
+    }
+
+    /// Add download job to the queue
+    ///
+    /// `job` can be called several times if it was paused and then resumed, and it's called
+    /// with interrupted handle to clean up its partial files if it was cancelled while paused.
+    /// `callback` is called once the job was finished or cancelled
+    pub fn push(
+        &self,
+        job: impl FnMut(&DownloadHandle) -> Result<DownloadStatus, ActionError> + Send + 'static,
+        callback: impl FnOnce(Result<DownloadStatus, ActionError>) + Send + 'static
+    ) {
+        #[allow(unused_must_use)] {
+            self.sender.send(QueueMsg::Push(Box::new(job), Box::new(callback)));
+        }
+    }
+
+    /// Pause currently running download
+    #[inline]
+    pub fn pause(&self) {
+        self.handle.pause();
+    }
+
+    /// Resume paused download
+    #[inline]
+    #[allow(unused_must_use)]
+    pub fn resume(&self) {
+        self.sender.send(QueueMsg::Resume);
+    }
+
+    /// Cancel running or paused download
+    #[inline]
+    #[allow(unused_must_use)]
+    pub fn cancel(&self) {
+        self.handle.cancel();
+        self.sender.send(QueueMsg::Cancel);
+    }
+
+    fn worker(receiver: Receiver<QueueMsg>, handle: DownloadHandle) {
+        let mut jobs: VecDeque<(DownloadJob, DownloadCallback)> = VecDeque::new();
+
+        let mut paused = false;
+        let mut cancelled = false;
+
+        loop {
+            // Wait for new messages if there's nothing to run
+            let msg = if paused || jobs.is_empty() {
+                match receiver.recv() {
+                    Ok(msg) => Some(msg),
+                    Err(_) => break
+                }
+            } else {
+                receiver.try_recv().ok()
+            };
+
+            match msg {
+                Some(QueueMsg::Push(job, callback)) => jobs.push_back((job, callback)),
+
+                Some(QueueMsg::Resume) => paused = false,
+
+                // Running downloads are cancelled by the handle
+                Some(QueueMsg::Cancel) if paused => {
+                    paused = false;
+                    cancelled = true;
+                }
+
+                _ => ()
+            }
+
+            if paused {
+                continue;
+            }
+
+            let Some((mut job, callback)) = jobs.pop_front() else {
+                continue;
+            };
+
+            // Drop interruptions requested while nothing was downloading
+            if cancelled {
+                handle.cancel();
+            } else {
+                handle.reset();
+            }
+
+            cancelled = false;
+
+            match job(&handle) {
+                Ok(DownloadStatus::Paused) => {
+                    paused = true;
+
+                    jobs.push_front((job, callback));
+                }
+
+                result => callback(result)
+            }
+        }
+    }
+}
+
+impl Default for DownloadQueue {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufRead, BufReader};
+    use std::net::TcpListener;
+    use std::path::PathBuf;
+
+    use super::*;
+
+    const FILE_SIZE: usize = 16 * DOWNLOAD_CHUNK_SIZE + 123;
+
+    fn file_content() -> Vec<u8> {
+        (0..FILE_SIZE).map(|i| (i % 251) as u8).collect()
+    }
+
+    /// Start HTTP server which serves test file and supports `Range` header
+    fn serve_file() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        std::thread::spawn(move || {
+            let content = file_content();
+
+            for stream in listener.incoming() {
+                let Ok(mut stream) = stream else {
+                    continue;
+                };
+
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut start = 0;
+
+                loop {
+                    let mut line = String::new();
+
+                    if reader.read_line(&mut line).unwrap_or(0) == 0 || line == "\r\n" {
+                        break;
+                    }
+
+                    if let Some(range) = line.to_lowercase().strip_prefix("range: bytes=") {
+                        start = range.trim().trim_end_matches('-').parse().unwrap();
+                    }
+                }
+
+                let head = if start >= content.len() {
+                    String::from("HTTP/1.1 416 Range Not Satisfiable\r\nContent-Length: 0\r\nConnection: close\r\n\r\n")
+                } else if start > 0 {
+                    format!("HTTP/1.1 206 Partial Content\r\nContent-Length: {}\r\nConnection: close\r\n\r\n", content.len() - start)
+                } else {
+                    format!("HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n", content.len())
+                };
+
+                let body = content.get(start..).unwrap_or_default();
+
+                // Client closes connection when download is interrupted
+                let _ = stream.write_all(head.as_bytes()).and_then(|_| stream.write_all(body));
+            }
+        });
+
+        format!("http://{addr}/file.bin")
+    }
+
+    fn temp_file(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("borb-launcher-download-{name}-{}", std::process::id()));
+
+        let _ = std::fs::remove_file(&path);
+
+        path
+    }
+
+    /// Updater which requests interruption when given amount of bytes was downloaded
+    fn interrupt_after(handle: &DownloadHandle, bytes: u64, status: DownloadStatus) -> impl Fn(InstallerUpdate) {
+        let handle = handle.clone();
+
+        move |update| {
+            if let InstallerUpdate::DownloadingProgress(curr, _) = update {
+                if curr >= bytes {
+                    match status {
+                        DownloadStatus::Paused => handle.pause(),
+                        DownloadStatus::Cancelled => handle.cancel(),
+                        DownloadStatus::Finished => ()
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn pauses_and_continues_download() {
+        let url = serve_file();
+        let file = temp_file("pause");
+        let handle = DownloadHandle::default();
+
+        let status = handle.download(&url, &file, interrupt_after(&handle, 3 * DOWNLOAD_CHUNK_SIZE as u64, DownloadStatus::Paused)).unwrap();
+
+        assert_eq!(status, DownloadStatus::Paused);
+        assert_eq!(std::fs::metadata(&file).unwrap().len(), 3 * DOWNLOAD_CHUNK_SIZE as u64);
+
+        handle.reset();
+
+        let status = handle.download(&url, &file, |_| ()).unwrap();
+
+        assert_eq!(status, DownloadStatus::Finished);
+        assert_eq!(std::fs::read(&file).unwrap(), file_content());
+
+        // Already downloaded file is not downloaded again
+        let status = handle.download(&url, &file, |_| ()).unwrap();
+
+        assert_eq!(status, DownloadStatus::Finished);
+        assert_eq!(std::fs::read(&file).unwrap(), file_content());
+
+        std::fs::remove_file(file).unwrap();
+    }
+
+    #[test]
+    fn cancel_removes_partial_file() {
+        let url = serve_file();
+        let file = temp_file("cancel");
+        let handle = DownloadHandle::default();
+
+        let status = handle.download(&url, &file, interrupt_after(&handle, DOWNLOAD_CHUNK_SIZE as u64, DownloadStatus::Cancelled)).unwrap();
+
+        assert_eq!(status, DownloadStatus::Cancelled);
+        assert!(!file.exists());
+    }
+
+    #[test]
+    fn cancel_while_paused_removes_partial_file() {
+        let url = serve_file();
+        let file = temp_file("cancel-paused");
+        let handle = DownloadHandle::default();
+
+        let status = handle.download(&url, &file, interrupt_after(&handle, DOWNLOAD_CHUNK_SIZE as u64, DownloadStatus::Paused)).unwrap();
+
+        assert_eq!(status, DownloadStatus::Paused);
+        assert!(file.exists());
+
+        handle.cancel();
+
+        let status = handle.download(&url, &file, |_| ()).unwrap();
+
+        assert_eq!(status, DownloadStatus::Cancelled);
+        assert!(!file.exists());
+    }
+}
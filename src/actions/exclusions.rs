@@ -0,0 +1,140 @@
+use std::path::Path;
+
+fn matches_chars(pattern: &[char], path: &[char]) -> bool {
+    match pattern {
+        [] => path.is_empty(),
+
+        ['*', '*', rest @ ..] => {
+            // `**/` also matches no folders at all
+            let without_slash = rest.strip_prefix(&['/']);
+
+            (0..=path.len()).any(|i| {
+                matches_chars(rest, &path[i..]) ||
+                matches!(without_slash, Some(rest) if matches_chars(rest, &path[i..]))
+            })
+        }
+
+        ['*', rest @ ..] => {
+            for i in 0..=path.len() {
+                if matches_chars(rest, &path[i..]) {
+                    return true;
+                }
+
+                if path.get(i) == Some(&'/') {
+                    break;
+                }
+            }
+
+            false
+        }
+
+        ['?', rest @ ..] => match path {
+            [c, path @ ..] if *c != '/' => matches_chars(rest, path),
+            _ => false
+        },
+
+        [p, rest @ ..] => match path {
+            [c, path @ ..] if c == p => matches_chars(rest, path),
+            _ => false
+        }
+    }
+}
+
+/// Check if game file path (relative to the game folder) matches glob pattern
+///
+/// `*` matches any characters except `/`, `**` matches any characters including `/`
+/// and `?` matches a single character except `/`. Patterns without `/` are matched
+/// against file names, so `*.ini` matches `.ini` files in all the folders.
+/// Leading `/` matches the game folder, so `/*.ini` matches only `.ini` files in it
+pub fn matches(pattern: &str, path: &Path) -> bool {
+    let pattern = pattern.trim();
+
+    let anchored = pattern.starts_with('/');
+    let pattern = pattern.trim_start_matches('/');
+
+    if pattern.is_empty() {
+        return false;
+    }
+
+    let pattern = pattern.chars().collect::<Vec<_>>();
+
+    let path = path.to_string_lossy()
+        .replace('\\', "/")
+        .chars()
+        .collect::<Vec<_>>();
+
+    if !anchored && !pattern.contains(&'/') {
+        let name_start = path.iter()
+            .rposition(|c| *c == '/')
+            .map(|i| i + 1)
+            .unwrap_or(0);
+
+        return matches_chars(&pattern, &path[name_start..]);
+    }
+
+    matches_chars(&pattern, &path)
+}
+
+/// Check if game file is excluded from verification and repair by any of the given patterns
+#[inline]
+pub fn is_excluded(patterns: &[String], path: &Path) -> bool {
+    patterns.iter().any(|pattern| matches(pattern, path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn double_star_matches_zero_folders() {
+        assert!(matches("Engine/**/*.ini", Path::new("Engine/Engine.ini")));
+        assert!(matches("Engine/**/*.ini", Path::new("Engine/Config/Engine.ini")));
+        assert!(matches("Engine/**/*.ini", Path::new("Engine/Config/Windows/Engine.ini")));
+
+        assert!(!matches("Engine/**/*.ini", Path::new("Game/Engine.ini")));
+    }
+
+    #[test]
+    fn star_doesnt_cross_folders() {
+        assert!(matches("Engine/*.ini", Path::new("Engine/Engine.ini")));
+
+        assert!(!matches("Engine/*.ini", Path::new("Engine/Config/Engine.ini")));
+        assert!(!matches("Engine/*", Path::new("Engine/Config/Engine.ini")));
+    }
+
+    #[test]
+    fn question_mark_doesnt_match_slash() {
+        assert!(matches("Engine?.ini", Path::new("Engine1.ini")));
+
+        assert!(!matches("Engine?Engine.ini", Path::new("Engine/Engine.ini")));
+    }
+
+    #[test]
+    fn name_patterns_match_any_folder() {
+        assert!(matches("*.ini", Path::new("Engine.ini")));
+        assert!(matches("*.ini", Path::new("Engine/Config/Engine.ini")));
+        assert!(matches("Engine.ini", Path::new("Engine/Config/Engine.ini")));
+
+        assert!(!matches("*.ini", Path::new("Engine/Config/Engine.ini.bak")));
+        assert!(!matches("Config", Path::new("Engine/Config/Engine.ini")));
+    }
+
+    #[test]
+    fn leading_slash_matches_game_folder() {
+        assert!(matches("/Engine.ini", Path::new("Engine.ini")));
+        assert!(matches("/*.ini", Path::new("Engine.ini")));
+        assert!(matches("/Engine/*.ini", Path::new("Engine/Engine.ini")));
+
+        assert!(!matches("/Engine.ini", Path::new("Engine/Engine.ini")));
+        assert!(!matches("/*.ini", Path::new("Engine/Engine.ini")));
+    }
+
+    #[test]
+    fn empty_patterns_match_nothing() {
+        assert!(!matches("", Path::new("Engine.ini")));
+        assert!(!matches(" / ", Path::new("Engine.ini")));
+
+        assert!(!is_excluded(&[], Path::new("Engine.ini")));
+        assert!(is_excluded(&[String::new(), String::from("*.ini")], Path::new("Engine.ini")));
+    }
+}
@@ -0,0 +1,129 @@
+use anime_launcher_sdk::pgr::states::LauncherState;
+
+use crate::*;
+
+use super::*;
+
+/// Step of the launcher setup chain
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SetupStep {
+    DownloadWine,
+    CreatePrefix,
+    InstallMfc140,
+    InstallFonts,
+    DownloadGame
+}
+
+impl SetupStep {
+    /// Get step which fixes given launcher state
+    pub fn for_state(state: &LauncherState) -> Option<Self> {
+        match state {
+            LauncherState::Launch => None,
+
+            LauncherState::WineNotInstalled => Some(Self::DownloadWine),
+            LauncherState::PrefixNotExists  => Some(Self::CreatePrefix),
+
+            LauncherState::Mfc140NotInstalled   => Some(Self::InstallMfc140),
+            LauncherState::FontsNotInstalled(_) => Some(Self::InstallFonts),
+
+            LauncherState::GameUpdateAvailable(_) |
+            LauncherState::GameNotInstalled(_) => Some(Self::DownloadGame)
+        }
+    }
+
+    /// Get translated step name
+    pub fn name(&self) -> String {
+        match self {
+            Self::DownloadWine  => tr("download-wine"),
+            Self::CreatePrefix  => tr("create-prefix"),
+            Self::InstallMfc140 => tr("install-mfc140"),
+            Self::InstallFonts  => tr("install-fonts"),
+            Self::DownloadGame  => tr("download-game")
+        }
+    }
+}
+
+fn get_state() -> Result<LauncherState, ActionError> {
+    let state = if crate::offline::is_offline() {
+        crate::offline::local_state()
+    } else {
+        LauncherState::get_from_config(|_| {})
+    };
+
+    state.map_err(|err| ActionError::from_err("launcher-state-updating-error", err))
+}
+
+/// Perform launcher actions one after another until the game is ready to launch
+///
+/// Launcher state is re-evaluated after each step. Chain is stopped on the first failed step,
+/// or if the step didn't change launcher state. Download steps can be paused or cancelled using the given handle
+pub fn fix_everything(handle: &DownloadHandle, updater: impl Fn(ActionUpdate) + Clone + Send + 'static) -> Result<DownloadStatus, ActionError> {
+    let mut previous_step = None;
+    let mut number: u32 = 1;
+
+    loop {
+        let state = get_state()?;
+
+        let Some(step) = SetupStep::for_state(&state) else {
+            return Ok(DownloadStatus::Finished);
+        };
+
+        // Stop if the last step didn't change anything
+        if previous_step == Some(step) {
+            tracing::error!("Setup step didn't change launcher state: {step:?}");
+
+            return Err(ActionError::new(tr_args("setup-step-failed", [
+                ("step", step.name().into())
+            ]), Some(tr("setup-step-not-fixed"))));
+        }
+
+        tracing::info!("Performing setup step {number}: {step:?}");
+
+        updater(ActionUpdate::Step(Some(tr_args("setup-step", [
+            ("number", number.into()),
+            ("step", step.name().into())
+        ]))));
+
+        let result = match state {
+            LauncherState::WineNotInstalled => download_wine(handle, updater.clone()),
+
+            LauncherState::PrefixNotExists => create_prefix(updater.clone())
+                .map(|_| DownloadStatus::Finished),
+
+            LauncherState::Mfc140NotInstalled => install_mfc140(updater.clone())
+                .map(|_| DownloadStatus::Finished),
+
+            LauncherState::FontsNotInstalled(fonts) => install_fonts(fonts, updater.clone())
+                .map(|_| DownloadStatus::Finished),
+
+            LauncherState::GameUpdateAvailable(diff) |
+            LauncherState::GameNotInstalled(diff) => download_diff(diff, handle, updater.clone()),
+
+            LauncherState::Launch => unreachable!()
+        };
+
+        match result {
+            Ok(DownloadStatus::Finished) => (),
+
+            // Download was paused or cancelled by user
+            Ok(status) => return Ok(status),
+
+            Err(err) => {
+                tracing::error!("Setup step failed: {step:?}: {err}");
+
+                let mut report = err.title;
+
+                if let Some(description) = err.description {
+                    report = format!("{report}\n\n{description}");
+                }
+
+                return Err(ActionError::new(tr_args("setup-step-failed", [
+                    ("step", step.name().into())
+                ]), Some(report)));
+            }
+        }
+
+        previous_step = Some(step);
+        number += 1;
+    }
+}
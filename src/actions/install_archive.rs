@@ -0,0 +1,197 @@
+use std::path::Path;
+
+use serde::{Serialize, Deserialize};
+
+use anime_launcher_sdk::anime_game_core::installer::archives::Archive;
+
+use crate::*;
+
+use super::*;
+
+/// Game archive required by the game version diff
+///
+/// Saved every time the diff is found online, so local archives
+/// can be verified without network access
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExpectedArchive {
+    /// Installed game version the diff was found for. `None` if the game was not installed
+    pub installed_version: Option<String>,
+
+    /// Game version installed by the archive
+    pub version: String,
+
+    pub file_name: String,
+    pub size: Option<u64>
+}
+
+impl ExpectedArchive {
+    /// Get archive required by the diff. `None` is returned if the game is already updated
+    pub fn from_diff(diff: &VersionDiff) -> Option<Self> {
+        let (installed_version, version) = match diff {
+            VersionDiff::Latest(_) => return None,
+
+            VersionDiff::Outdated { current, latest, .. } => (Some(current.to_string()), latest.to_string()),
+            VersionDiff::NotInstalled { latest, .. } => (None, latest.to_string())
+        };
+
+        Some(Self {
+            installed_version,
+            version,
+            file_name: diff.file_name()?,
+            size: diff.downloaded_size()
+        })
+    }
+
+    /// Remember archive required by the diff found online
+    pub fn remember(diff: &VersionDiff) {
+        let result = match Self::from_diff(diff) {
+            Some(archive) => serde_json::to_vec_pretty(&archive)
+                .map_err(std::io::Error::from)
+                .and_then(|archive| std::fs::write(EXPECTED_ARCHIVE_FILE.as_path(), archive)),
+
+            // Game is already updated so no archive is needed
+            None => return Self::forget()
+        };
+
+        if let Err(err) = result {
+            tracing::error!("Failed to save expected game archive: {err}");
+        }
+    }
+
+    fn forget() {
+        if EXPECTED_ARCHIVE_FILE.exists() {
+            if let Err(err) = std::fs::remove_file(EXPECTED_ARCHIVE_FILE.as_path()) {
+                tracing::error!("Failed to remove expected game archive: {err}");
+            }
+        }
+    }
+
+    /// Remember archive required by the launcher state evaluated online
+    pub fn remember_state(state: &LauncherState) {
+        match state {
+            LauncherState::GameUpdateAvailable(diff) |
+            LauncherState::GameNotInstalled(diff) => Self::remember(diff),
+
+            // Game is installed and updated
+            LauncherState::Launch => Self::forget(),
+
+            _ => ()
+        }
+    }
+
+    /// Get archive required by the last diff found online
+    ///
+    /// `None` is returned if there's no such diff, or installed game version was changed since then
+    pub fn cached() -> Option<Self> {
+        let archive = std::fs::read(EXPECTED_ARCHIVE_FILE.as_path()).ok()?;
+        let archive = serde_json::from_slice::<Self>(&archive).ok()?;
+
+        let installed_version = GAME.is_installed()
+            .then(|| GAME.get_version().ok())
+            .flatten()
+            .map(|version| version.to_string());
+
+        (archive.installed_version == installed_version).then_some(archive)
+    }
+
+    /// Get archive required to install the game or its update
+    ///
+    /// Game version diff is requested from the network unless the launcher is offline.
+    /// Archive required by the last diff found online is used if the network is not available
+    pub fn get() -> Result<Self, ActionError> {
+        if !crate::offline::is_offline() {
+            match GAME.try_get_diff() {
+                Ok(diff) => {
+                    Self::remember(&diff);
+
+                    return Self::from_diff(&diff)
+                        .ok_or_else(|| ActionError::new(tr("archive-not-needed"), None));
+                }
+
+                Err(err) => tracing::warn!("Failed to find game diff, using the last found one: {err}")
+            }
+        }
+
+        Self::cached().ok_or_else(|| ActionError::new(tr("archive-no-game-version"), Some(tr("archive-no-cached-version"))))
+    }
+}
+
+/// Check that the local archive is the one required by the game version diff
+pub fn verify_archive(archive: &Path, expected: &ExpectedArchive) -> Result<(), ActionError> {
+    let name = archive.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    if name != expected.file_name {
+        return Err(ActionError::new(tr("archive-version-mismatch"), Some(tr_args("archive-expected-name", [
+            ("expected", expected.file_name.clone().into()),
+            ("actual", name.into())
+        ]))));
+    }
+
+    let size = archive.metadata()
+        .map_err(|err| ActionError::from_err("archive-opening-error", err))?
+        .len();
+
+    if let Some(expected) = expected.size {
+        if size != expected {
+            return Err(ActionError::new(tr("archive-version-mismatch"), Some(tr_args("archive-expected-size", [
+                ("expected", prettify_bytes(expected).into()),
+                ("actual", prettify_bytes(size).into())
+            ]))));
+        }
+    }
+
+    Ok(())
+}
+
+/// Check that the game version required by the archive was installed
+///
+/// Diff installation can do more than unpacking its archive, so
+/// the installed version is checked after unpacking archives ourselves
+pub(crate) fn verify_installed_version(expected: &ExpectedArchive) -> Result<(), ActionError> {
+    let installed = GAME.get_version()
+        .map(|version| version.to_string())
+        .unwrap_or_default();
+
+    if installed != expected.version {
+        tracing::error!("Installed game version {installed} doesn't match the archive version {}", expected.version);
+
+        return Err(ActionError::new(tr("archive-installed-version-mismatch"), Some(tr_args("archive-installed-versions", [
+            ("expected", expected.version.clone().into()),
+            ("installed", installed.into())
+        ]))));
+    }
+
+    Ok(())
+}
+
+/// Install game or its update from a local archive without network access
+///
+/// Archive is verified using `verify_archive` and unpacked into the game folder.
+/// Installed game version is checked afterwards, so an archive which needs
+/// more than unpacking can't silently leave the game outdated
+pub fn install_archive(archive: &Path, expected: &ExpectedArchive, updater: impl Fn(ActionUpdate)) -> Result<(), ActionError> {
+    let config = Config::get().map_err(|err| ActionError::from_err("config-reading-error", err))?;
+
+    tracing::info!("Installing game from local archive: {:?}", archive);
+
+    verify_archive(archive, expected)?;
+
+    updater(ActionUpdate::Installer(InstallerUpdate::UnpackingStarted(config.game.path.clone())));
+
+    let mut archive = Archive::open(archive)
+        .map_err(|err| ActionError::from_err("archive-opening-error", err))?;
+
+    if let Err(err) = archive.extract(&config.game.path) {
+        tracing::error!("Unpacking failed: {err}");
+
+        return Err(ActionError::from_err("unpacking-failed", err));
+    }
+
+    updater(ActionUpdate::Installer(InstallerUpdate::UnpackingFinished));
+
+    IntegrityCache::invalidate();
+
+    verify_installed_version(expected)
+}
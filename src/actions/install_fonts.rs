@@ -0,0 +1,46 @@
+use anime_launcher_sdk::wincompatlib::prelude::*;
+
+use crate::*;
+
+use super::*;
+
+/// Install given fonts to the wine prefix
+pub fn install_fonts(fonts: Vec<Font>, updater: impl Fn(ActionUpdate)) -> Result<(), ActionError> {
+    let config = Config::get().map_err(|err| ActionError::from_err("config-reading-error", err))?;
+
+    let wine = match config.get_selected_wine() {
+        Ok(Some(wine)) => wine,
+
+        Ok(None) => {
+            tracing::error!("Failed to get selected wine executable");
+
+            return Err(ActionError::new(tr("failed-get-selected-wine"), None));
+        }
+
+        Err(err) => {
+            tracing::error!("Failed to get selected wine executable: {err}");
+
+            return Err(ActionError::from_err("failed-get-selected-wine", err));
+        }
+    };
+
+    let wine = wine
+        .to_wine(config.components.path, Some(config.game.wine.builds.join(&wine.name)))
+        .with_prefix(&config.game.wine.prefix)
+        .with_loader(WineLoader::Current)
+        .with_arch(WineArch::Win64);
+
+    for (i, font) in fonts.iter().copied().enumerate() {
+        updater(ActionUpdate::Stage(ActionStage::InstallingFont(font.name().to_string())));
+
+        if let Err(err) = wine.install_font(font) {
+            tracing::error!("Failed to install font: {}", font.name());
+
+            return Err(ActionError::from_err("downloading-failed", err));
+        }
+
+        updater(ActionUpdate::Progress(i as u64 + 1, fonts.len() as u64));
+    }
+
+    Ok(())
+}
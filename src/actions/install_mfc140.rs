@@ -0,0 +1,20 @@
+use anime_launcher_sdk::anime_game_core::patches::mfc140;
+
+use crate::*;
+
+use super::*;
+
+/// Install mfc140 libraries to the wine prefix
+pub fn install_mfc140(updater: impl Fn(ActionUpdate)) -> Result<(), ActionError> {
+    let config = Config::get().map_err(|err| ActionError::from_err("config-reading-error", err))?;
+
+    updater(ActionUpdate::Stage(ActionStage::InstallingMfc140));
+
+    if let Err(err) = mfc140::install(config.get_wine_prefix_path(), config.launcher.temp) {
+        tracing::error!("Failed to install mfc140: {err}");
+
+        return Err(ActionError::from_err("downloading-failed", err));
+    }
+
+    Ok(())
+}
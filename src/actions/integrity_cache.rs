@@ -0,0 +1,179 @@
+use std::collections::{HashMap, HashSet};
+use std::os::unix::fs::MetadataExt;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use serde::{Serialize, Deserialize};
+
+use crate::*;
+
+/// Metadata of the game file at the moment it passed verification
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct CachedFile {
+    size: u64,
+
+    /// Modification time in nanoseconds since unix epoch
+    mtime: u64,
+
+    inode: u64,
+
+    /// Hash from the integrity files list the file was verified against
+    md5: String
+}
+
+impl CachedFile {
+    fn new(path: &Path, md5: &str) -> Option<Self> {
+        let metadata = path.metadata().ok()?;
+
+        Some(Self {
+            size: metadata.len(),
+            mtime: metadata.modified().ok()?.duration_since(UNIX_EPOCH).ok()?.as_nanos() as u64,
+            inode: metadata.ino(),
+            md5: md5.to_string()
+        })
+    }
+}
+
+/// Game files which passed full verification and weren't changed since
+///
+/// Stored in the `.integrity-cache.json` file in the launcher folder
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct IntegrityCache {
+    /// File the cache is stored in
+    #[serde(skip)]
+    file: PathBuf,
+
+    /// Game folder the files were verified in
+    game_path: PathBuf,
+
+    /// Cached files with paths relative to the game folder
+    files: HashMap<PathBuf, CachedFile>,
+
+    /// Files which passed fast verification which wasn't finished yet.
+    /// Fast verification only compares file sizes, so only paths are stored
+    fast_verified: HashSet<PathBuf>,
+
+    /// Verification was paused, cancelled or is still running. Interrupted
+    /// verification is resumed from the cache even if full verification is forced
+    pub interrupted: bool
+}
+
+impl IntegrityCache {
+    /// Create empty cache of the given game folder
+    #[inline]
+    fn new(file: &Path, game_path: &Path) -> Self {
+        Self {
+            file: file.to_path_buf(),
+            game_path: game_path.to_path_buf(),
+            files: HashMap::new(),
+            fast_verified: HashSet::new(),
+            interrupted: false
+        }
+    }
+
+    /// Load cache of the given game folder from the launcher folder
+    #[inline]
+    pub fn load(game_path: &Path) -> Self {
+        Self::load_from(INTEGRITY_CACHE_FILE.as_path(), game_path)
+    }
+
+    /// Load cache of the given game folder from the given file. Empty cache
+    /// is returned if there's no cache file or it was made for another folder
+    pub fn load_from(file: &Path, game_path: &Path) -> Self {
+        let cache = std::fs::read(file)
+            .ok()
+            .and_then(|cache| serde_json::from_slice::<Self>(&cache).ok());
+
+        match cache {
+            Some(cache) if cache.game_path == game_path => Self {
+                file: file.to_path_buf(),
+                ..cache
+            },
+
+            _ => Self::new(file, game_path)
+        }
+    }
+
+    /// Forget all the verified files
+    #[inline]
+    pub fn clear(&mut self) {
+        *self = Self::new(&self.file, &self.game_path);
+    }
+
+    /// Save cache, logging an error if it failed
+    pub fn save(&self) {
+        let result = serde_json::to_vec(self)
+            .map_err(std::io::Error::from)
+            .and_then(|cache| std::fs::write(&self.file, cache));
+
+        if let Err(err) = result {
+            tracing::error!("Failed to save integrity cache: {err}");
+        }
+    }
+
+    /// Remove cache file so the next verification re-hashes all the files
+    pub fn invalidate() {
+        if INTEGRITY_CACHE_FILE.exists() {
+            tracing::debug!("Invalidating integrity cache");
+
+            if let Err(err) = std::fs::remove_file(INTEGRITY_CACHE_FILE.as_path()) {
+                tracing::error!("Failed to remove integrity cache: {err}");
+            }
+        }
+    }
+
+    /// Check if the file was verified and its metadata wasn't changed since
+    pub fn is_verified(&self, file: &repairer::IntegrityFile) -> bool {
+        match self.files.get(&file.path) {
+            Some(cached) => CachedFile::new(&self.game_path.join(&file.path), &file.md5).as_ref() == Some(cached),
+            None => false
+        }
+    }
+
+    /// Remember that the file passed verification
+    pub fn insert(&mut self, file: &repairer::IntegrityFile) {
+        if let Some(cached) = CachedFile::new(&self.game_path.join(&file.path), &file.md5) {
+            self.files.insert(file.path.clone(), cached);
+        }
+    }
+
+    #[inline]
+    pub fn remove(&mut self, file: &repairer::IntegrityFile) {
+        self.files.remove(&file.path);
+    }
+
+    /// Get integrity files which weren't verified against the same hash
+    ///
+    /// Files which have the same hash in the new integrity files list
+    /// weren't changed by the game update since they were verified
+    pub fn changed_files(&self, files: Vec<repairer::IntegrityFile>) -> Vec<repairer::IntegrityFile> {
+        files.into_iter()
+            .filter(|file| self.files.get(&file.path).map(|cached| &cached.md5) != Some(&file.md5))
+            .collect()
+    }
+
+    /// Check if the file passed unfinished fast verification and its size wasn't changed since
+    pub fn is_fast_verified(&self, file: &repairer::IntegrityFile) -> bool {
+        self.fast_verified.contains(&file.path) && self.game_path.join(&file.path)
+            .metadata()
+            .is_ok_and(|metadata| metadata.len() == file.size)
+    }
+
+    /// Remember that the file passed fast verification
+    #[inline]
+    pub fn insert_fast(&mut self, file: &repairer::IntegrityFile) {
+        self.fast_verified.insert(file.path.clone());
+    }
+
+    #[inline]
+    pub fn remove_fast(&mut self, file: &repairer::IntegrityFile) {
+        self.fast_verified.remove(&file.path);
+    }
+
+    /// Forget files verified by the finished fast verification
+    #[inline]
+    pub fn clear_fast(&mut self) {
+        self.fast_verified.clear();
+    }
+}
@@ -0,0 +1,114 @@
+use std::path::{Path, PathBuf};
+
+use serde::{Serialize, Deserialize};
+
+use crate::*;
+
+use super::*;
+use super::repair_game::get_integrity_files;
+
+/// Game file entry of the integrity manifest
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManifestFile {
+    /// Path relative to the game folder
+    pub path: PathBuf,
+
+    pub md5: String,
+    pub size: u64,
+
+    /// URL the file is repaired from. Not needed for verification
+    #[serde(default)]
+    pub base_url: String
+}
+
+/// Local copy of the official integrity files list
+///
+/// Used to verify game files without network access
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IntegrityManifest {
+    /// Installed game version at the moment the manifest was exported
+    pub game_version: Option<String>,
+
+    pub files: Vec<ManifestFile>
+}
+
+impl IntegrityManifest {
+    /// Get official integrity files list. Requires network access
+    pub fn fetch() -> Result<Self, ActionError> {
+        if crate::offline::is_offline() {
+            return Err(ActionError::new(tr("manifest-export-offline"), None));
+        }
+
+        let files = get_integrity_files()?;
+
+        Ok(Self {
+            game_version: GAME.get_version().ok().map(|version| version.to_string()),
+
+            files: files.into_iter()
+                .map(|file| ManifestFile {
+                    path: file.path,
+                    md5: file.md5,
+                    size: file.size,
+                    base_url: file.base_url
+                })
+                .collect()
+        })
+    }
+
+    pub fn load(path: &Path) -> Result<Self, ActionError> {
+        let manifest = std::fs::read(path)
+            .map_err(|err| ActionError::from_err("manifest-reading-error", err))?;
+
+        serde_json::from_slice(&manifest)
+            .map_err(|err| ActionError::from_err("manifest-reading-error", err))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), ActionError> {
+        let manifest = serde_json::to_string_pretty(self)
+            .map_err(|err| ActionError::from_err("manifest-saving-error", err))?;
+
+        std::fs::write(path, manifest)
+            .map_err(|err| ActionError::from_err("manifest-saving-error", err))
+    }
+
+    /// Get warning if the manifest was exported for another game version
+    pub fn version_warning(&self) -> Option<ActionError> {
+        let manifest_version = self.game_version.as_ref()?;
+        let installed_version = GAME.get_version().ok()?.to_string();
+
+        if manifest_version == &installed_version {
+            return None;
+        }
+
+        tracing::warn!("Integrity manifest was exported for {manifest_version}, but {installed_version} is installed");
+
+        Some(ActionError::new(tr("manifest-version-mismatch"), Some(tr_args("manifest-versions", [
+            ("manifest", manifest_version.clone().into()),
+            ("installed", installed_version.into())
+        ]))))
+    }
+
+    pub fn integrity_files(self) -> Vec<repairer::IntegrityFile> {
+        self.files.into_iter()
+            .map(|file| repairer::IntegrityFile {
+                path: file.path,
+                md5: file.md5,
+                size: file.size,
+                base_url: file.base_url
+            })
+            .collect()
+    }
+}
+
+/// Save official integrity files list to the local manifest file
+///
+/// Returns number of exported files
+pub fn export_manifest(path: &Path) -> Result<usize, ActionError> {
+    let manifest = IntegrityManifest::fetch()?;
+
+    manifest.save(path)?;
+
+    tracing::info!("Exported {} integrity files to {:?}", manifest.files.len(), path);
+
+    Ok(manifest.files.len())
+}
@@ -0,0 +1,225 @@
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use anime_launcher_sdk::anime_game_core::prelude::*;
+use anime_launcher_sdk::pgr::states::LauncherState;
+
+use crate::i18n::*;
+use crate::network::SpeedMeter;
+
+pub mod downloads;
+pub mod download_diff;
+pub mod download_wine;
+pub mod install_archive;
+pub mod create_prefix;
+pub mod install_mfc140;
+pub mod install_fonts;
+pub mod repair_game;
+pub mod integrity_cache;
+pub mod exclusions;
+pub mod verify_update;
+pub mod manifest;
+pub mod fix_everything;
+pub mod scheduler;
+
+pub use downloads::{DownloadQueue, DownloadHandle, DownloadStatus};
+pub use download_diff::{download_diff, download_preview, DownloadPreview};
+pub use download_wine::download_wine;
+pub use install_archive::{install_archive, verify_archive, ExpectedArchive};
+pub use create_prefix::create_prefix;
+pub use install_mfc140::install_mfc140;
+pub use install_fonts::install_fonts;
+pub use integrity_cache::IntegrityCache;
+pub use repair_game::{repair_game, verify_game, verify_game_files, verify_with_manifest, repair_files, IntegrityReport, ReportedFile};
+pub use manifest::{export_manifest, IntegrityManifest};
+pub use verify_update::verify_update;
+pub use fix_everything::{fix_everything, SetupStep};
+
+/// Machine-readable launcher state name
+pub fn state_name(state: &LauncherState) -> &'static str {
+    match state {
+        LauncherState::Launch => "launch",
+
+        LauncherState::WineNotInstalled => "wine-not-installed",
+        LauncherState::PrefixNotExists  => "prefix-not-exists",
+
+        LauncherState::Mfc140NotInstalled   => "mfc140-not-installed",
+        LauncherState::FontsNotInstalled(_) => "fonts-not-installed",
+
+        LauncherState::GameUpdateAvailable(_) => "game-update-available",
+        LauncherState::GameNotInstalled(_)    => "game-not-installed"
+    }
+}
+
+/// Event reported by the launcher actions
+///
+/// Actions are UI-agnostic and report everything through these events
+/// so they could be driven by the main window, headless commands or anything else
+#[derive(Debug)]
+pub enum ActionUpdate {
+    /// Installer state forwarded from the SDK
+    Installer(InstallerUpdate),
+
+    /// Action started new stage
+    Stage(ActionStage),
+
+    /// Chained action started new step. Contains translated step caption,
+    /// or `None` when the chain is finished
+    Step(Option<String>),
+
+    /// (current, total) progress of the current stage
+    ///
+    /// Measured in bytes or in items depending on `ActionStage::in_bytes`
+    Progress(u64, u64),
+
+    /// Non-critical error which didn't stop the action
+    Error(ActionError),
+
+    /// Action result which should be shown to the user
+    Notice {
+        /// Translated notice title
+        title: String,
+
+        description: Option<String>
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ActionStage {
+    VerifyingFiles,
+    RepairingFiles,
+    CreatingPrefix,
+    InstallingMfc140,
+
+    /// Font name
+    InstallingFont(String),
+
+    /// Waiting before the next download attempt
+    RetryingDownload {
+        attempt: u32,
+        attempts: u32
+    },
+
+    /// Mirror host
+    DownloadingFromMirror(String)
+}
+
+impl ActionStage {
+    /// Get translated stage caption
+    pub fn caption(&self) -> String {
+        match self {
+            Self::VerifyingFiles   => tr("verifying-files"),
+            Self::RepairingFiles   => tr("repairing-files"),
+            Self::CreatingPrefix   => tr("create-prefix"),
+            Self::InstallingMfc140 => tr("install-mfc140"),
+
+            Self::InstallingFont(name) => format!("{} {name}", tr("downloading")),
+
+            Self::RetryingDownload { attempt, attempts } => tr_args("retrying-download", [
+                ("attempt", (*attempt).into()),
+                ("attempts", (*attempts).into())
+            ]),
+
+            Self::DownloadingFromMirror(host) => tr_args("downloading-from-mirror", [
+                ("mirror", host.clone().into())
+            ])
+        }
+    }
+
+    /// Check if stage progress is measured in bytes.
+    /// Otherwise it's measured in processed items
+    #[inline]
+    pub fn in_bytes(&self) -> bool {
+        matches!(self, Self::VerifyingFiles | Self::DownloadingFromMirror(_))
+    }
+}
+
+/// Error which stopped an action or was reported by it
+#[derive(Debug, Clone)]
+pub struct ActionError {
+    /// Translated error title
+    pub title: String,
+
+    /// Error details
+    pub description: Option<String>
+}
+
+impl ActionError {
+    #[inline]
+    pub fn new(title: String, description: Option<String>) -> Self {
+        Self {
+            title,
+            description
+        }
+    }
+
+    /// Create error with translated title and given error details
+    #[inline]
+    pub fn from_err(title: &str, err: impl std::fmt::Display) -> Self {
+        Self::new(tr(title), Some(err.to_string()))
+    }
+}
+
+impl std::fmt::Display for ActionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.description {
+            Some(description) => write!(f, "{}: {description}", self.title),
+            None => write!(f, "{}", self.title)
+        }
+    }
+}
+
+impl std::error::Error for ActionError {}
+
+/// Get installer updates handler which forwards them to the action updater
+/// and converts installer errors into `ActionUpdate::Error`
+///
+/// Returned flag is set to `true` when installer reported an error
+pub(crate) fn installer_updater(
+    updater: impl Fn(ActionUpdate) + Clone + Send + 'static,
+    downloading_error: &'static str,
+    unpacking_error: &'static str
+) -> (impl Fn(InstallerUpdate) + Clone + Send + 'static, Arc<AtomicBool>) {
+    let failed = Arc::new(AtomicBool::new(false));
+    let speed = Arc::new(Mutex::new(SpeedMeter::default()));
+
+    let handler = {
+        let failed = failed.clone();
+
+        move |state: InstallerUpdate| {
+            match &state {
+                InstallerUpdate::DownloadingStarted(_) => *speed.lock().unwrap() = SpeedMeter::default(),
+                InstallerUpdate::DownloadingProgress(curr, _) => speed.lock().unwrap().update(*curr),
+
+                // Remember download speed to estimate next downloads time
+                InstallerUpdate::DownloadingFinished => {
+                    if let Some(speed) = speed.lock().unwrap().average_speed() {
+                        crate::network::speed::save_recent_speed(speed);
+                    }
+                }
+
+                InstallerUpdate::DownloadingError(err) => {
+                    tracing::error!("Downloading failed: {err}");
+
+                    failed.store(true, Ordering::Relaxed);
+
+                    updater(ActionUpdate::Error(ActionError::from_err(downloading_error, err)));
+                }
+
+                InstallerUpdate::UnpackingError(err) => {
+                    tracing::error!("Unpacking failed: {err}");
+
+                    failed.store(true, Ordering::Relaxed);
+
+                    updater(ActionUpdate::Error(ActionError::from_err(unpacking_error, err)));
+                }
+
+                _ => ()
+            }
+
+            updater(ActionUpdate::Installer(state));
+        }
+    };
+
+    (crate::network::throttle_installer(handler), failed)
+}
@@ -0,0 +1,486 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::fs::Metadata;
+
+use serde::Serialize;
+
+use crate::*;
+use crate::settings::Settings;
+
+use super::*;
+use super::exclusions::is_excluded;
+use super::manifest::IntegrityManifest;
+
+/// Verification cache is saved after this number of verified files, so
+/// verification is resumed even if the launcher was closed while it was running
+const CACHE_SAVE_INTERVAL: usize = 100;
+
+/// Game file which differs from the integrity files list
+#[derive(Debug, Clone, Serialize)]
+pub struct ReportedFile {
+    /// Path relative to the game folder
+    pub path: PathBuf,
+
+    /// Size from the integrity files list
+    pub expected_size: Option<u64>,
+
+    /// Size of the local file
+    pub actual_size: Option<u64>
+}
+
+/// Result of the game files verification
+#[derive(Debug, Clone, Serialize)]
+pub struct IntegrityReport {
+    pub game_path: PathBuf,
+
+    /// Files which exist but didn't pass verification
+    pub mismatched: Vec<ReportedFile>,
+
+    /// Files which are listed in the integrity files list but don't exist
+    pub missing: Vec<ReportedFile>,
+
+    /// Files which exist in the game folder but aren't listed in the integrity files list
+    pub extra: Vec<ReportedFile>,
+
+    /// Files excluded from repair which differ from the official ones
+    pub excluded: Vec<ReportedFile>,
+
+    /// Integrity files of mismatched and missing files, needed to repair them
+    #[serde(skip)]
+    pub broken: Vec<repairer::IntegrityFile>
+}
+
+impl IntegrityReport {
+    /// Check if all the listed game files passed verification
+    #[inline]
+    pub fn is_clean(&self) -> bool {
+        self.broken.is_empty()
+    }
+
+    /// Get integrity files of broken files except ones with given relative paths
+    pub fn broken_except(&self, excluded: &[PathBuf]) -> Vec<repairer::IntegrityFile> {
+        self.broken.iter()
+            .filter(|file| !excluded.contains(&file.path))
+            .cloned()
+            .collect()
+    }
+
+    /// Serialize report for bug reports
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+/// Game files verification parameters
+#[derive(Debug, Clone)]
+struct VerifyOptions {
+    game_path: PathBuf,
+
+    /// Compare only file sizes instead of hashes
+    fast: bool,
+
+    threads: usize,
+
+    /// Re-hash files which weren't changed since the last verification
+    force_full: bool,
+
+    /// Patterns of files excluded from repair
+    exclusions: Vec<String>,
+
+    /// File the verification cache is stored in
+    cache_file: PathBuf
+}
+
+impl VerifyOptions {
+    /// Get verification parameters from the launcher config and settings
+    fn get() -> Result<Self, ActionError> {
+        let config = Config::get().map_err(|err| ActionError::from_err("config-reading-error", err))?;
+        let settings = Settings::get().unwrap_or_default();
+
+        Ok(Self {
+            game_path: config.game.path,
+            fast: config.launcher.repairer.fast,
+            threads: config.launcher.repairer.threads as usize,
+            force_full: settings.repair.force_full_verify,
+            exclusions: settings.repair.exclusions,
+            cache_file: INTEGRITY_CACHE_FILE.clone()
+        })
+    }
+}
+
+/// Get all the files in the given folder with paths relative to the `root`
+pub(crate) fn list_files(root: &Path, folder: &Path, files: &mut Vec<(PathBuf, Metadata)>) -> std::io::Result<()> {
+    for entry in std::fs::read_dir(folder)? {
+        let entry = entry?;
+        let metadata = entry.metadata()?;
+
+        if metadata.is_dir() {
+            list_files(root, &entry.path(), files)?;
+        }
+
+        else if let Ok(path) = entry.path().strip_prefix(root) {
+            files.push((path.to_path_buf(), metadata));
+        }
+    }
+
+    Ok(())
+}
+
+/// Get official integrity files list
+pub(crate) fn get_integrity_files() -> Result<Vec<repairer::IntegrityFile>, ActionError> {
+    repairer::try_get_integrity_files().map_err(|err| {
+        tracing::error!("Failed to get inregrity failes: {err}");
+
+        ActionError::from_err("integrity-files-getting-error", err)
+    })
+}
+
+/// Verify game files without repairing them
+///
+/// Verification can be paused or cancelled using the given handle, in which case `None` is returned.
+/// Already verified files are remembered, so the next verification continues where this one stopped
+pub fn verify_game(handle: &DownloadHandle, updater: impl Fn(ActionUpdate)) -> Result<Option<IntegrityReport>, ActionError> {
+    updater(ActionUpdate::Stage(ActionStage::VerifyingFiles));
+
+    verify(get_integrity_files()?, true, &VerifyOptions::get()?, handle, updater)
+}
+
+/// Verify only given game files without repairing them
+///
+/// Extra files are not reported. Verification can be interrupted the same way as in `verify_game`
+pub fn verify_game_files(files: Vec<repairer::IntegrityFile>, handle: &DownloadHandle, updater: impl Fn(ActionUpdate)) -> Result<Option<IntegrityReport>, ActionError> {
+    updater(ActionUpdate::Stage(ActionStage::VerifyingFiles));
+
+    verify(files, false, &VerifyOptions::get()?, handle, updater)
+}
+
+/// Verify game files against the local integrity manifest without network access
+///
+/// Verification can be interrupted the same way as in `verify_game`
+pub fn verify_with_manifest(manifest: &Path, handle: &DownloadHandle, updater: impl Fn(ActionUpdate)) -> Result<Option<IntegrityReport>, ActionError> {
+    updater(ActionUpdate::Stage(ActionStage::VerifyingFiles));
+
+    let manifest = IntegrityManifest::load(manifest)?;
+
+    // Manifest could be exported for another game version
+    if let Some(warning) = manifest.version_warning() {
+        updater(ActionUpdate::Error(warning));
+    }
+
+    verify(manifest.integrity_files(), true, &VerifyOptions::get()?, handle, updater)
+}
+
+fn verify(
+    files: Vec<repairer::IntegrityFile>,
+    report_extra: bool,
+    options: &VerifyOptions,
+    handle: &DownloadHandle,
+    updater: impl Fn(ActionUpdate)
+) -> Result<Option<IntegrityReport>, ActionError> {
+    let game_path = &options.game_path;
+    let fast = options.fast;
+
+    updater(ActionUpdate::Progress(0, 0));
+
+    let mut total = 0;
+
+    for file in &files {
+        total += file.size;
+    }
+
+    let listed = files.iter()
+        .map(|file| file.path.clone())
+        .collect::<HashSet<_>>();
+
+    // Files which weren't changed since the last full verification are not re-hashed.
+    // Fast verification only compares file sizes, so it skips only files which passed
+    // the previous fast verification if it wasn't finished
+    let mut cache = IntegrityCache::load_from(&options.cache_file, game_path);
+
+    if !fast && !cache.interrupted && options.force_full {
+        cache.clear();
+    }
+
+    let (unchanged, files) = files.into_iter()
+        .partition::<Vec<_>, _>(|file| if fast {
+            cache.is_fast_verified(file)
+        } else {
+            cache.is_verified(file)
+        });
+
+    // Cache is saved while verification is running,
+    // so it's resumed if the launcher was closed
+    if !fast {
+        cache.interrupted = true;
+    }
+
+    if !unchanged.is_empty() {
+        tracing::debug!("Skipping verification of {} unchanged files", unchanged.len());
+    }
+
+    let mut report = IntegrityReport {
+        game_path: game_path.clone(),
+        mismatched: Vec::new(),
+        missing: Vec::new(),
+        extra: Vec::new(),
+        excluded: Vec::new(),
+        broken: Vec::new()
+    };
+
+    let mut processed = unchanged.iter().map(|file| file.size).sum::<u64>();
+    let mut verified = 0;
+
+    scheduler::largest_first(files, options.threads, |file| file.size, |file| {
+        // Remaining files are skipped when verification is interrupted
+        if handle.interruption().is_some() {
+            return None;
+        }
+
+        Some(if fast {
+            file.fast_verify(game_path)
+        } else {
+            file.verify(game_path)
+        })
+    }, |file, status| {
+        let Some(status) = status else {
+            return;
+        };
+
+        processed += file.size;
+        verified += 1;
+
+        match (fast, status) {
+            (true, true)   => cache.insert_fast(file),
+            (true, false)  => cache.remove_fast(file),
+            (false, true)  => cache.insert(file),
+            (false, false) => cache.remove(file)
+        }
+
+        if verified % CACHE_SAVE_INTERVAL == 0 {
+            cache.save();
+        }
+
+        if !status {
+            let actual_size = game_path.join(&file.path).metadata()
+                .map(|metadata| metadata.len())
+                .ok();
+
+            let reported = ReportedFile {
+                path: file.path.clone(),
+                expected_size: Some(file.size),
+                actual_size
+            };
+
+            // Excluded files are verified only to warn user that they were modified
+            if is_excluded(&options.exclusions, &file.path) {
+                report.excluded.push(reported);
+            }
+
+            else {
+                if actual_size.is_some() {
+                    report.mismatched.push(reported);
+                } else {
+                    report.missing.push(reported);
+                }
+
+                report.broken.push(file.clone());
+            }
+        }
+
+        updater(ActionUpdate::Progress(processed, total));
+    });
+
+    if let Some(status) = handle.interruption() {
+        tracing::info!("Verification interrupted: {status:?}");
+
+        cache.save();
+
+        return Ok(None);
+    }
+
+    if fast {
+        cache.clear_fast();
+    } else {
+        cache.interrupted = false;
+    }
+
+    cache.save();
+
+    // Extra files are reported only for information, so listing errors are not critical
+    if report_extra {
+        let mut local_files = Vec::new();
+
+        if let Err(err) = list_files(game_path, game_path, &mut local_files) {
+            tracing::warn!("Failed to list game files: {err}");
+        }
+
+        report.extra = local_files.into_iter()
+            .filter(|(path, _)| !listed.contains(path) && !is_excluded(&options.exclusions, path))
+            .map(|(path, metadata)| ReportedFile {
+                path,
+                expected_size: None,
+                actual_size: Some(metadata.len())
+            })
+            .collect();
+    }
+
+    report.mismatched.sort_by(|a, b| a.path.cmp(&b.path));
+    report.missing.sort_by(|a, b| a.path.cmp(&b.path));
+    report.extra.sort_by(|a, b| a.path.cmp(&b.path));
+    report.excluded.sort_by(|a, b| a.path.cmp(&b.path));
+
+    if !report.excluded.is_empty() {
+        let files = report.excluded.iter().fold(String::new(), |acc, file| acc + &format!("- {}\n", file.path.to_string_lossy()));
+
+        tracing::warn!("Excluded files differ from the official ones:\n{files}");
+
+        updater(ActionUpdate::Error(ActionError::new(tr("excluded-files-modified"), Some(files))));
+    }
+
+    if !report.is_clean() {
+        tracing::warn!("Found broken files:\n{}", report.broken.iter().fold(String::new(), |acc, file| acc + &format!("- {}\n", file.path.to_string_lossy())));
+    }
+
+    Ok(Some(report))
+}
+
+/// Re-download given game files
+///
+/// Repair can be paused or cancelled using the given handle between files.
+/// Each failed file is reported with `ActionUpdate::Error`, and an error listing
+/// all of them is returned when the repair is finished
+pub fn repair_files(files: Vec<repairer::IntegrityFile>, handle: &DownloadHandle, updater: impl Fn(ActionUpdate)) -> Result<DownloadStatus, ActionError> {
+    let config = Config::get().map_err(|err| ActionError::from_err("config-reading-error", err))?;
+
+    let total = files.len() as u64;
+
+    let mut failed = Vec::new();
+
+    updater(ActionUpdate::Stage(ActionStage::RepairingFiles));
+    updater(ActionUpdate::Progress(0, total));
+
+    for (i, file) in files.into_iter().enumerate() {
+        if let Some(status) = handle.interruption() {
+            tracing::info!("Repair interrupted: {status:?}");
+
+            return Ok(status);
+        }
+
+        tracing::debug!("Repairing file: {}", file.path.to_string_lossy());
+
+        if let Err(err) = file.repair(&config.game.path) {
+            tracing::error!("Failed to repair game file: {err}");
+
+            updater(ActionUpdate::Error(ActionError::from_err("game-file-repairing-error", err)));
+
+            failed.push(file.path);
+        }
+
+        updater(ActionUpdate::Progress(i as u64 + 1, total));
+    }
+
+    if !failed.is_empty() {
+        return Err(ActionError::new(tr_args("game-files-repairing-failed", [
+            ("count", failed.len().into())
+        ]), Some(failed.iter().fold(String::new(), |acc, path| acc + &format!("- {}\n", path.to_string_lossy())))));
+    }
+
+    Ok(DownloadStatus::Finished)
+}
+
+/// Verify game files and repair broken ones
+///
+/// Repair can be paused or cancelled using the given handle. Interrupted repair
+/// doesn't verify already verified files again when it's started next time
+pub fn repair_game(handle: &DownloadHandle, updater: impl Fn(ActionUpdate)) -> Result<DownloadStatus, ActionError> {
+    let Some(report) = verify_game(handle, &updater)? else {
+        return Ok(handle.interruption().unwrap_or(DownloadStatus::Cancelled));
+    };
+
+    if report.is_clean() {
+        return Ok(DownloadStatus::Finished);
+    }
+
+    repair_files(report.broken, handle, updater)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/integrity-manifest.json");
+
+    /// Create game folder which differs from the fixture manifest
+    fn create_game_folder(game_path: &Path) {
+        let files: [(&str, &[u8]); 5] = [
+            ("Game.exe", b"game binary"),
+
+            // Modified files
+            ("Engine/Engine.ini", b"[Engine]\nQuality=Low\n"),
+            ("Engine/User.ini", b"[User]\nLanguage=de\n"),
+
+            // Files which aren't listed in the manifest
+            ("Saved/Screenshot.png", b"screenshot"),
+            ("Logs/Game.log", b"log")
+        ];
+
+        for (path, content) in files {
+            let path = game_path.join(path);
+
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, content).unwrap();
+        }
+    }
+
+    #[test]
+    fn reports_mismatched_missing_and_extra_files() {
+        let temp = std::env::temp_dir().join(format!("borb-launcher-verify-{}", std::process::id()));
+
+        if temp.exists() {
+            std::fs::remove_dir_all(&temp).unwrap();
+        }
+
+        let game_path = temp.join("game");
+
+        create_game_folder(&game_path);
+
+        let options = VerifyOptions {
+            game_path: game_path.clone(),
+            fast: false,
+            threads: 2,
+            force_full: true,
+            exclusions: vec![String::from("User.ini"), String::from("*.log")],
+            cache_file: temp.join("integrity-cache.json")
+        };
+
+        let manifest = IntegrityManifest::load(Path::new(MANIFEST)).unwrap();
+
+        let report = verify(manifest.integrity_files(), true, &options, &DownloadHandle::default(), |_| {})
+            .unwrap()
+            .expect("Verification was interrupted");
+
+        std::fs::remove_dir_all(&temp).unwrap();
+
+        let paths = |files: &[ReportedFile]| files.iter()
+            .map(|file| file.path.clone())
+            .collect::<Vec<_>>();
+
+        assert_eq!(paths(&report.mismatched), [PathBuf::from("Engine/Engine.ini")]);
+        assert_eq!(paths(&report.missing), [PathBuf::from("Data/data.pak")]);
+        assert_eq!(paths(&report.extra), [PathBuf::from("Saved/Screenshot.png")]);
+        assert_eq!(paths(&report.excluded), [PathBuf::from("Engine/User.ini")]);
+
+        assert_eq!(report.mismatched[0].expected_size, Some(22));
+        assert_eq!(report.mismatched[0].actual_size, Some(21));
+        assert_eq!(report.missing[0].actual_size, None);
+
+        // Excluded files are never repaired
+        let broken = report.broken.iter()
+            .map(|file| file.path.clone())
+            .collect::<Vec<_>>();
+
+        assert_eq!(broken.len(), 2);
+        assert!(broken.contains(&PathBuf::from("Engine/Engine.ini")));
+        assert!(broken.contains(&PathBuf::from("Data/data.pak")));
+    }
+}
@@ -0,0 +1,60 @@
+//! Parallel work scheduling used by the game files verifier
+//!
+//! This module depends only on the standard library
+//! so it's shared with the `verify` benchmark
+
+use std::cmp::Reverse;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+
+/// Process items in parallel, largest ones first
+///
+/// Workers take items from the shared queue one by one, so a thread which got
+/// a few huge files doesn't finish long after the rest. Starting with the largest items
+/// leaves only small ones for the end, so all the threads finish at about the same time
+///
+/// `on_result` is called in the current thread in completion order
+pub fn largest_first<T, R>(
+    mut items: Vec<T>,
+    threads: usize,
+    size: impl Fn(&T) -> u64,
+    work: impl Fn(&T) -> R + Sync,
+    mut on_result: impl FnMut(&T, R)
+) where
+    T: Sync,
+    R: Send
+{
+    items.sort_by_key(|item| Reverse(size(item)));
+
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    std::thread::scope(|scope| {
+        for _ in 0..threads.clamp(1, items.len().max(1)) {
+            let sender = sender.clone();
+
+            let items = &items;
+            let next = &next;
+            let work = &work;
+
+            scope.spawn(move || loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+
+                let Some(item) = items.get(i) else {
+                    break;
+                };
+
+                if sender.send((i, work(item))).is_err() {
+                    break;
+                }
+            });
+        }
+
+        // Receiver will return Err when all the workers are finished
+        drop(sender);
+
+        while let Ok((i, result)) = receiver.recv() {
+            on_result(&items[i], result);
+        }
+    });
+}
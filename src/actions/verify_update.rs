@@ -0,0 +1,54 @@
+use crate::*;
+
+use super::*;
+use super::repair_game::get_integrity_files;
+
+/// Verify game files changed by the installed update and repair broken ones
+///
+/// Changed files are found by comparing the new integrity files list with the verification
+/// cache loaded before the update was installed. Files which weren't verified before are
+/// verified as well, so files the update failed to create are found too.
+///
+/// Result is reported with `ActionUpdate::Notice`. Verification is just skipped if it
+/// was interrupted using the given handle, so the installed update is not downloaded again
+pub fn verify_update(previous: &IntegrityCache, handle: &DownloadHandle, updater: impl Fn(ActionUpdate)) -> Result<(), ActionError> {
+    let changed = previous.changed_files(get_integrity_files()?);
+
+    tracing::info!("Verifying {} files changed by the update", changed.len());
+
+    let Some(report) = verify_game_files(changed, handle, &updater)? else {
+        tracing::warn!("Update verification was interrupted");
+
+        return Ok(());
+    };
+
+    if report.is_clean() {
+        updater(ActionUpdate::Notice {
+            title: tr("update-verified"),
+            description: None
+        });
+
+        return Ok(());
+    }
+
+    let broken = report.broken.iter()
+        .map(|file| format!("- {}", file.path.to_string_lossy()))
+        .collect::<Vec<_>>();
+
+    tracing::warn!("Repairing files broken by the update:\n{}", broken.join("\n"));
+
+    if repair_files(report.broken, handle, &updater)? != DownloadStatus::Finished {
+        tracing::warn!("Update repair was interrupted");
+
+        return Ok(());
+    }
+
+    updater(ActionUpdate::Notice {
+        title: tr_args("update-files-repaired", [
+            ("count", broken.len().into())
+        ]),
+        description: Some(broken.join("\n"))
+    });
+
+    Ok(())
+}
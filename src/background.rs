@@ -46,7 +46,7 @@ pub fn download_background() -> anyhow::Result<()> {
     let mut downloader = Downloader::new(info.uri)?
         .with_continue_downloading(false);
 
-    if let Err(err) = downloader.download(crate::BACKGROUND_FILE.as_path(), |_, _| {}) {
+    if let Err(err) = downloader.download(crate::BACKGROUND_FILE.as_path(), crate::network::throttle_downloader(|_, _| {})) {
         anyhow::bail!(err);
     }
 
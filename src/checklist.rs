@@ -0,0 +1,223 @@
+use anime_launcher_sdk::wincompatlib::prelude::*;
+use anime_launcher_sdk::anime_game_core::patches::mfc140;
+
+use crate::*;
+use crate::i18n::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckStatus {
+    Passed,
+    Failed,
+
+    /// Check can't be performed yet (e.g. it depends on another failed check)
+    Pending
+}
+
+/// Action which fixes failed check
+#[derive(Debug, Clone)]
+pub enum CheckFix {
+    DownloadWine,
+    CreatePrefix,
+    InstallMfc140,
+    InstallFonts(Vec<Font>),
+    DownloadDiff(VersionDiff),
+    OpenPreferences
+}
+
+impl CheckFix {
+    /// Get translated fix button label
+    pub fn label(&self) -> String {
+        match self {
+            Self::DownloadWine    => tr("download-wine"),
+            Self::CreatePrefix    => tr("create-prefix"),
+            Self::InstallMfc140   => tr("install-mfc140"),
+            Self::InstallFonts(_) => tr("install-fonts"),
+            Self::DownloadDiff(_) => tr("download"),
+            Self::OpenPreferences => tr("preferences")
+        }
+    }
+}
+
+/// Single launcher setup requirement
+#[derive(Debug, Clone)]
+pub struct Check {
+    /// Translated check name
+    pub name: String,
+
+    pub status: CheckStatus,
+
+    /// Translated reason of the check status
+    pub reason: String,
+
+    pub fix: Option<CheckFix>
+}
+
+impl Check {
+    #[inline]
+    fn passed(name: String, reason: String) -> Self {
+        Self {
+            name,
+            status: CheckStatus::Passed,
+            reason,
+            fix: None
+        }
+    }
+
+    #[inline]
+    fn failed(name: String, reason: String, fix: Option<CheckFix>) -> Self {
+        Self {
+            name,
+            status: CheckStatus::Failed,
+            reason,
+            fix
+        }
+    }
+
+    #[inline]
+    fn pending(name: String, reason: String) -> Self {
+        Self {
+            name,
+            status: CheckStatus::Pending,
+            reason,
+            fix: None
+        }
+    }
+}
+
+/// Check everything the launcher state depends on except the game version
+///
+/// Unlike `LauncherState` which stops on the first unmet requirement,
+/// all the checks are performed. Only local files are checked
+pub fn local_checks() -> Vec<Check> {
+    let config = Config::get().unwrap_or_else(|_| CONFIG.clone());
+
+    let mut checks = Vec::new();
+
+    // Wine build
+
+    let wine_installed = match config.get_selected_wine() {
+        Ok(Some(wine)) if config.game.wine.builds.join(&wine.name).exists() => {
+            checks.push(Check::passed(tr("wine"), wine.title));
+
+            true
+        }
+
+        Ok(Some(wine)) => {
+            checks.push(Check::failed(tr("wine"), tr_args("check-not-downloaded", [
+                ("name", wine.title.into())
+            ]), Some(CheckFix::DownloadWine)));
+
+            false
+        }
+
+        Ok(None) => {
+            checks.push(Check::failed(tr("wine"), tr("check-wine-not-selected"), Some(CheckFix::OpenPreferences)));
+
+            false
+        }
+
+        Err(err) => {
+            checks.push(Check::failed(tr("wine"), err.to_string(), Some(CheckFix::OpenPreferences)));
+
+            false
+        }
+    };
+
+    // Wine prefix
+
+    let prefix = config.get_wine_prefix_path();
+    let prefix_exists = prefix.join("drive_c").exists();
+
+    if prefix_exists {
+        checks.push(Check::passed(tr("wine-prefix"), prefix.to_string_lossy().to_string()));
+    } else if wine_installed {
+        checks.push(Check::failed(tr("wine-prefix"), tr("check-prefix-not-exists"), Some(CheckFix::CreatePrefix)));
+    } else {
+        checks.push(Check::pending(tr("wine-prefix"), tr("check-requires-wine")));
+    }
+
+    // mfc140 and fonts are installed to the prefix
+
+    if !prefix_exists {
+        checks.push(Check::pending(String::from("mfc140"), tr("check-requires-prefix")));
+        checks.push(Check::pending(tr("fonts"), tr("check-requires-prefix")));
+    }
+
+    else {
+        if mfc140::is_installed(&prefix) {
+            checks.push(Check::passed(String::from("mfc140"), tr("check-installed")));
+        } else {
+            checks.push(Check::failed(String::from("mfc140"), tr("check-not-installed"), Some(CheckFix::InstallMfc140)));
+        }
+
+        let missing_fonts = Font::iterator()
+            .filter(|font| !font.is_installed(&prefix))
+            .collect::<Vec<_>>();
+
+        if missing_fonts.is_empty() {
+            checks.push(Check::passed(tr("fonts"), tr("check-installed")));
+        }
+
+        for font in missing_fonts {
+            checks.push(Check::failed(font.name().to_string(), tr("check-font-not-installed"), Some(CheckFix::InstallFonts(vec![font]))));
+        }
+    }
+
+    // DXVK is optional, so it doesn't affect launcher state
+
+    match config.get_selected_dxvk() {
+        Ok(Some(dxvk)) if config.game.dxvk.builds.join(&dxvk.name).exists() => {
+            checks.push(Check::passed(String::from("DXVK"), dxvk.name));
+        }
+
+        Ok(Some(dxvk)) => {
+            checks.push(Check::failed(String::from("DXVK"), tr_args("check-not-downloaded", [
+                ("name", dxvk.name.into())
+            ]), Some(CheckFix::OpenPreferences)));
+        }
+
+        Ok(None) => {
+            checks.push(Check::passed(String::from("DXVK"), tr("check-dxvk-not-used")));
+        }
+
+        Err(err) => {
+            checks.push(Check::failed(String::from("DXVK"), err.to_string(), Some(CheckFix::OpenPreferences)));
+        }
+    }
+
+    // Game has no anti-cheat patch, so there's nothing to check here
+
+    checks
+}
+
+/// Check game version. Requires network access
+pub fn game_check() -> Check {
+    if crate::offline::is_offline() {
+        return Check::pending(tr("game"), tr("check-offline"));
+    }
+
+    match GAME.try_get_diff() {
+        Ok(VersionDiff::Latest(version)) => Check::passed(tr("game"), tr_args("check-game-latest", [
+            ("version", version.to_string().into())
+        ])),
+
+        Ok(diff @ VersionDiff::Outdated { .. }) => {
+            let VersionDiff::Outdated { current, latest, .. } = &diff else {
+                unreachable!();
+            };
+
+            let reason = tr_args("check-game-outdated", [
+                ("current", current.to_string().into()),
+                ("latest", latest.to_string().into())
+            ]);
+
+            Check::failed(tr("game"), reason, Some(CheckFix::DownloadDiff(diff)))
+        }
+
+        Ok(diff @ VersionDiff::NotInstalled { .. }) => {
+            Check::failed(tr("game"), tr("check-game-not-installed"), Some(CheckFix::DownloadDiff(diff)))
+        }
+
+        Err(err) => Check::failed(tr("game"), err.to_string(), None)
+    }
+}
@@ -0,0 +1,121 @@
+use anime_launcher_sdk::components::*;
+use anime_launcher_sdk::components::loader::ComponentsLoader;
+
+use crate::*;
+use crate::i18n::*;
+
+use super::ExitCode;
+
+pub fn components() -> ExitCode {
+    let config = match Config::get() {
+        Ok(config) => config,
+        Err(err) => {
+            tracing::error!("Failed to read config: {err}");
+
+            eprintln!("{}: {err}", tr("config-reading-error"));
+
+            return ExitCode::Failure;
+        }
+    };
+
+    // Components index can't be synced without the network
+    if crate::offline::is_offline() {
+        println!("Components index is not updated in offline mode");
+    }
+
+    else if !sync_index(&config) {
+        return ExitCode::Failure;
+    }
+
+    // List downloaded components
+    println!();
+    println!("{}:", tr("wine-version"));
+
+    match wine::get_downloaded(&config.components.path, &config.game.wine.builds) {
+        Ok(groups) => {
+            for version in groups.into_iter().flat_map(|group| group.versions) {
+                let selected = config.game.wine.selected.as_ref() == Some(&version.name);
+
+                println!("  {} {}", if selected { "*" } else { "-" }, version.title);
+            }
+        }
+
+        Err(err) => {
+            eprintln!("{}: {err}", tr("downloaded-wine-list-failed"));
+
+            return ExitCode::Failure;
+        }
+    }
+
+    println!();
+    println!("{}:", tr("dxvk-version"));
+
+    let selected_dxvk = config.get_selected_dxvk().ok().flatten().map(|version| version.name);
+
+    match dxvk::get_downloaded(&config.components.path, &config.game.dxvk.builds) {
+        Ok(groups) => {
+            for version in groups.into_iter().flat_map(|group| group.versions) {
+                let selected = selected_dxvk.as_ref() == Some(&version.name);
+
+                println!("  {} {}", if selected { "*" } else { "-" }, version.name);
+            }
+        }
+
+        Err(err) => {
+            tracing::error!("Failed to list downloaded DXVK versions: {err}");
+
+            return ExitCode::Failure;
+        }
+    }
+
+    ExitCode::Success
+}
+
+/// Update components index. Returns `false` if it failed
+fn sync_index(config: &Config) -> bool {
+    let components = ComponentsLoader::new(&config.components.path);
+
+    match components.is_sync(&config.components.servers) {
+        Ok(Some(_)) => {
+            println!("Components index is up to date");
+
+            true
+        }
+
+        Ok(None) => {
+            let mut synced = false;
+
+            for host in &config.components.servers {
+                match components.sync(host) {
+                    Ok(changes) => {
+                        println!("{}", tr("components-index-updated"));
+
+                        for line in changes {
+                            println!("- {line}");
+                        }
+
+                        synced = true;
+
+                        break;
+                    }
+
+                    Err(err) => {
+                        tracing::error!("Failed to sync components index");
+
+                        eprintln!("{}: {err}", tr("components-index-sync-failed"));
+                    }
+                }
+            }
+
+            synced
+        }
+
+        Err(err) => {
+            tracing::error!("Failed to verify that components index synced");
+
+            eprintln!("{}: {err}", tr("components-index-verify-failed"));
+
+            false
+        }
+    }
+}
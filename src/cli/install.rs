@@ -0,0 +1,62 @@
+use std::sync::Arc;
+
+use anime_launcher_sdk::pgr::states::LauncherState;
+
+use crate::*;
+use crate::actions::{ActionUpdate, ActionError, DownloadHandle};
+
+use super::ExitCode;
+use super::progress::TerminalProgress;
+
+/// Perform launcher actions one after another until the game is ready to launch
+pub fn install() -> ExitCode {
+    let progress = Arc::new(TerminalProgress::default());
+
+    let result = actions::fix_everything(&DownloadHandle::default(), terminal_updater(&progress));
+
+    finish(&progress, result.map(|_| ()))
+}
+
+/// Perform action required by the given launcher state
+pub fn perform(state: LauncherState) -> ExitCode {
+    let progress = Arc::new(TerminalProgress::default());
+    let updater = terminal_updater(&progress);
+
+    let result = match state {
+        LauncherState::Launch => Ok(()),
+
+        LauncherState::WineNotInstalled => actions::download_wine(&DownloadHandle::default(), updater).map(|_| ()),
+        LauncherState::PrefixNotExists  => actions::create_prefix(updater),
+
+        LauncherState::Mfc140NotInstalled => actions::install_mfc140(updater),
+        LauncherState::FontsNotInstalled(fonts) => actions::install_fonts(fonts, updater),
+
+        LauncherState::GameUpdateAvailable(diff) |
+        LauncherState::GameNotInstalled(diff) => actions::download_diff(diff, &DownloadHandle::default(), updater).map(|_| ())
+    };
+
+    finish(&progress, result)
+}
+
+/// Finish progress output and print action error
+pub fn finish(progress: &TerminalProgress, result: Result<(), ActionError>) -> ExitCode {
+    progress.finish();
+
+    match result {
+        Ok(()) => ExitCode::Success,
+
+        Err(err) => {
+            eprintln!("{err}");
+
+            ExitCode::Failure
+        }
+    }
+}
+
+/// Terminal updater for the launcher actions
+#[inline]
+pub fn terminal_updater(progress: &Arc<TerminalProgress>) -> impl Fn(ActionUpdate) + Clone + Send + 'static {
+    let progress = progress.clone();
+
+    move |update| progress.update_from_action(update)
+}
@@ -0,0 +1,25 @@
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::*;
+
+use super::ExitCode;
+use super::progress::TerminalProgress;
+use super::install::{terminal_updater, finish};
+
+pub fn install_archive(archive: &Path) -> ExitCode {
+    let expected = match actions::ExpectedArchive::get() {
+        Ok(expected) => expected,
+        Err(err) => {
+            eprintln!("{err}");
+
+            return ExitCode::Failure;
+        }
+    };
+
+    let progress = Arc::new(TerminalProgress::default());
+
+    let result = actions::install_archive(archive, &expected, terminal_updater(&progress));
+
+    finish(&progress, result)
+}
@@ -0,0 +1,29 @@
+use anime_launcher_sdk::pgr::states::LauncherState;
+
+use crate::i18n::*;
+
+use super::ExitCode;
+
+pub fn launch() -> ExitCode {
+    let Some(state) = super::get_state(true) else {
+        return ExitCode::Failure;
+    };
+
+    if let LauncherState::Launch = state {
+        if let Err(err) = crate::game::run() {
+            tracing::error!("Failed to launch game: {err}");
+
+            eprintln!("{}: {err}", tr("game-launching-failed"));
+
+            return ExitCode::Failure;
+        }
+
+        ExitCode::Success
+    }
+
+    else {
+        eprintln!("Can't launch the game: {}", super::describe_state(&state));
+
+        ExitCode::ActionRequired
+    }
+}
@@ -0,0 +1,217 @@
+use std::path::PathBuf;
+
+use anime_launcher_sdk::pgr::states::LauncherState;
+
+pub mod progress;
+
+mod status;
+mod install;
+mod update;
+mod repair;
+mod verify;
+mod launch;
+mod components;
+mod install_archive;
+
+/// Exit codes returned by the headless commands
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExitCode {
+    Success = 0,
+
+    /// Command failed to perform its action
+    Failure = 1,
+
+    /// Wrong command usage
+    Usage = 2,
+
+    /// Launcher requires some action (e.g. `install` or `update`) before the game could be launched
+    ActionRequired = 3,
+
+    /// Another launcher process is running and owns launcher's files
+    AlreadyRunning = 4
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    Status,
+    Install,
+
+    /// Install game or its update from a local archive
+    InstallArchive(PathBuf),
+
+    Update,
+    Repair,
+
+    /// Verify game files without repairing them,
+    /// optionally using local integrity manifest
+    Verify(Option<PathBuf>),
+
+    /// Save official integrity files list to the local manifest
+    ExportManifest(PathBuf),
+
+    Launch,
+    Components,
+    Help
+}
+
+impl Command {
+    /// Check if command doesn't modify launcher files,
+    /// so it can be run alongside other launcher processes
+    #[inline]
+    pub fn is_read_only(&self) -> bool {
+        matches!(self, Self::Status | Self::Help)
+    }
+
+    /// Find headless command in the app's arguments
+    ///
+    /// Returns `None` if no command was given so the launcher should start its GUI,
+    /// and `Some(Err(..))` with an error message if command is wrong
+    pub fn from_args() -> Option<Result<Self, String>> {
+        let mut args = std::env::args().skip(1).filter(|arg| !arg.starts_with("--"));
+
+        let command = args.next()?;
+
+        Some(match command.as_str() {
+            "status"     => Ok(Self::Status),
+            "install"    => Ok(Self::Install),
+
+            "install-archive" => match args.next() {
+                Some(path) => Ok(Self::InstallArchive(PathBuf::from(path))),
+                None => Err(String::from("Missing archive path"))
+            },
+
+            "update"     => Ok(Self::Update),
+            "repair"     => Ok(Self::Repair),
+            "verify"     => Ok(Self::Verify(args.next().map(PathBuf::from))),
+
+            "export-manifest" => match args.next() {
+                Some(path) => Ok(Self::ExportManifest(PathBuf::from(path))),
+                None => Err(String::from("Missing manifest path"))
+            },
+
+            "launch"     => Ok(Self::Launch),
+            "components" => Ok(Self::Components),
+            "help"       => Ok(Self::Help),
+
+            _ => Err(format!("Unknown command: {command}"))
+        })
+    }
+}
+
+/// Run headless command
+pub fn run(command: Command) -> ExitCode {
+    tracing::info!("Running headless command: {command:?}");
+
+    match command {
+        Command::Status     => status::status(),
+        Command::Install    => install::install(),
+
+        Command::InstallArchive(path) => install_archive::install_archive(&path),
+
+        Command::Update     => update::update(),
+        Command::Repair     => repair::repair(),
+        Command::Verify(manifest) => verify::verify(manifest.as_deref()),
+
+        Command::ExportManifest(path) => verify::export_manifest(&path),
+
+        Command::Launch     => launch::launch(),
+        Command::Components => components::components(),
+
+        Command::Help => {
+            print_usage();
+
+            ExitCode::Success
+        }
+    }
+}
+
+pub fn print_usage() {
+    println!("Usage: anime-borb-launcher [command] [--debug] [--json] [--offline]");
+    println!();
+    println!("Commands:");
+    println!("  status      Show launcher state and game version. Use --json for machine-readable output");
+    println!("  install     Install everything needed to run the game");
+    println!("  install-archive <path>");
+    println!("              Install the game or its update from a local archive without downloading it");
+    println!("  update      Update the game if new version is available");
+    println!("  repair      Verify game files and repair broken ones");
+    println!("  verify [manifest]");
+    println!("              Verify game files and list broken ones without repairing them.");
+    println!("              Local integrity manifest is used instead of the network if given.");
+    println!("              Use --json for machine-readable report");
+    println!("  export-manifest <path>");
+    println!("              Save official game files integrity list to the local manifest");
+    println!("  launch      Launch the game if everything is installed");
+    println!("  components  Sync components index and list downloaded wine and DXVK versions");
+    println!("  help        Show this message");
+    println!();
+    println!("Run without command to open the launcher window");
+    println!();
+    println!("Options:");
+    println!("  --offline   Don't use the network. Cached background and components index and");
+    println!("              locally installed game version are used instead");
+    println!("  --debug     Show debug output");
+    println!();
+    println!("Exit codes:");
+    println!("  0  Success");
+    println!("  1  Command failed");
+    println!("  2  Wrong command usage");
+    println!("  3  Launcher requires installation or update, or game files are broken");
+    println!("  4  Launcher window or another command is already running");
+}
+
+/// Get launcher state. Only local files are checked in offline mode
+///
+/// If `remember` is true, then game archive required by the state found online is remembered
+/// so it could be installed offline later. Read-only commands don't own launcher files,
+/// so they must not write it
+pub(crate) fn evaluate_state(remember: bool) -> anyhow::Result<LauncherState> {
+    if crate::offline::is_offline() {
+        return crate::offline::local_state();
+    }
+
+    let state = LauncherState::get_from_config(|_| {});
+
+    if remember {
+        if let Ok(state) = &state {
+            crate::actions::ExpectedArchive::remember_state(state);
+        }
+    }
+
+    state
+}
+
+/// Get launcher state printing an error if it failed
+///
+/// `remember` is passed to `evaluate_state`
+pub(crate) fn get_state(remember: bool) -> Option<LauncherState> {
+    match evaluate_state(remember) {
+        Ok(state) => Some(state),
+        Err(err) => {
+            tracing::error!("Failed to update launcher state: {err}");
+
+            eprintln!("{}: {err}", crate::i18n::tr("launcher-state-updating-error"));
+
+            None
+        }
+    }
+}
+
+/// Human readable launcher state description
+pub(crate) fn describe_state(state: &LauncherState) -> String {
+    match state {
+        LauncherState::Launch => String::from("ready to launch"),
+
+        LauncherState::WineNotInstalled => String::from("wine is not installed"),
+        LauncherState::PrefixNotExists  => String::from("wine prefix is not created"),
+
+        LauncherState::Mfc140NotInstalled => String::from("mfc140 is not installed"),
+        LauncherState::FontsNotInstalled(fonts) => format!("fonts are not installed: {}", fonts.iter()
+            .map(|font| font.name())
+            .collect::<Vec<_>>()
+            .join(", ")),
+
+        LauncherState::GameUpdateAvailable(_) => String::from("game update is available"),
+        LauncherState::GameNotInstalled(_)    => String::from("game is not installed")
+    }
+}
@@ -0,0 +1,158 @@
+use std::io::Write;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use anime_launcher_sdk::anime_game_core::prelude::*;
+
+use crate::i18n::*;
+use crate::actions::ActionUpdate;
+use crate::network::SpeedMeter;
+use crate::network::speed::format_duration;
+
+/// Terminal replacement for the `ProgressBar` component
+///
+/// Prints progress into a single stdout line which is rewritten on every update
+pub struct TerminalProgress {
+    caption: Mutex<Option<String>>,
+
+    /// Add `(XX MB of YY MB)` suffix
+    display_fraction: AtomicBool,
+
+    speed: Mutex<SpeedMeter>
+}
+
+impl Default for TerminalProgress {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            caption: Mutex::new(None),
+            display_fraction: AtomicBool::new(true),
+            speed: Mutex::new(SpeedMeter::default())
+        }
+    }
+}
+
+impl TerminalProgress {
+    pub fn set_caption(&self, caption: Option<String>) {
+        let mut current = self.caption.lock().unwrap();
+
+        // Finish previous progress line
+        if current.is_some() {
+            println!();
+        }
+
+        *current = caption;
+
+        *self.speed.lock().unwrap() = SpeedMeter::default();
+    }
+
+    #[inline]
+    pub fn set_display_fraction(&self, value: bool) {
+        self.display_fraction.store(value, Ordering::Relaxed);
+    }
+
+    pub fn update(&self, curr: u64, total: u64) {
+        let caption = self.caption.lock().unwrap();
+
+        let mut line = caption.clone().unwrap_or_default();
+
+        let progress = if total > 0 {
+            curr as f64 / total as f64 * 100.0
+        } else {
+            0.0
+        };
+
+        line = format!("{line}: {progress:.2}%");
+
+        if self.display_fraction.load(Ordering::Relaxed) {
+            line = format!("{line} ({} of {})", prettify_bytes(curr), prettify_bytes(total));
+
+            let mut speed = self.speed.lock().unwrap();
+
+            speed.update(curr);
+
+            match (speed.speed(), speed.eta(total)) {
+                (Some(speed), Some(eta)) => line = format!("{line} - {}/s, {}", prettify_bytes(speed), tr_args("time-left", [("time", format_duration(eta).into())])),
+                (Some(speed), None) => line = format!("{line} - {}/s", prettify_bytes(speed)),
+
+                _ => ()
+            }
+        }
+
+        else {
+            line = format!("{line} ({curr} of {total})");
+        }
+
+        // Clear the rest of the previous line as it could be longer
+        print!("\r{line}\x1b[K");
+
+        #[allow(unused_must_use)] {
+            std::io::stdout().flush();
+        }
+    }
+
+    pub fn update_from_state(&self, state: InstallerUpdate) {
+        match state {
+            InstallerUpdate::CheckingFreeSpace(_)  => self.set_caption(Some(tr("checking-free-space"))),
+            InstallerUpdate::DownloadingStarted(_) => self.set_caption(Some(tr("downloading"))),
+            InstallerUpdate::UnpackingStarted(_)   => self.set_caption(Some(tr("unpacking"))),
+
+            InstallerUpdate::DownloadingProgress(curr, total) |
+            InstallerUpdate::UnpackingProgress(curr, total) => self.update(curr, total),
+
+            InstallerUpdate::DownloadingFinished => tracing::info!("Downloading finished"),
+            InstallerUpdate::UnpackingFinished   => tracing::info!("Unpacking finished"),
+
+            InstallerUpdate::DownloadingError(err) => tracing::error!("Downloading error: {:?}", err),
+            InstallerUpdate::UnpackingError(err) => tracing::error!("Unpacking error: {:?}", err)
+        }
+    }
+
+    pub fn update_from_action(&self, update: ActionUpdate) {
+        match update {
+            ActionUpdate::Installer(state) => {
+                // Installers always report progress in bytes
+                self.set_display_fraction(true);
+
+                self.update_from_state(state);
+            }
+
+            ActionUpdate::Stage(stage) => {
+                self.set_caption(Some(stage.caption()));
+                self.set_display_fraction(stage.in_bytes());
+            }
+
+            ActionUpdate::Step(Some(step)) => {
+                self.finish();
+
+                println!("{step}");
+            }
+
+            ActionUpdate::Step(None) => (),
+
+            ActionUpdate::Progress(curr, total) => self.update(curr, total),
+
+            ActionUpdate::Error(err) => {
+                self.finish();
+
+                eprintln!("{err}");
+            }
+
+            ActionUpdate::Notice { title, description } => {
+                self.finish();
+
+                println!("{title}");
+
+                if let Some(description) = description {
+                    println!("{description}");
+                }
+            }
+        }
+    }
+
+    /// Finish current progress line
+    #[inline]
+    pub fn finish(&self) {
+        self.set_caption(None);
+    }
+}
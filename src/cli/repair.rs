@@ -0,0 +1,16 @@
+use std::sync::Arc;
+
+use crate::*;
+use crate::actions::DownloadHandle;
+
+use super::ExitCode;
+use super::progress::TerminalProgress;
+use super::install::{terminal_updater, finish};
+
+pub fn repair() -> ExitCode {
+    let progress = Arc::new(TerminalProgress::default());
+
+    let result = actions::repair_game(&DownloadHandle::default(), terminal_updater(&progress));
+
+    finish(&progress, result.map(|_| ()))
+}
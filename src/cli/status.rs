@@ -0,0 +1,220 @@
+use anime_launcher_sdk::components::loader::ComponentsLoader;
+
+use anime_launcher_sdk::pgr::states::LauncherState;
+
+use crate::*;
+
+use super::ExitCode;
+
+pub fn status() -> ExitCode {
+    // Print machine-readable status
+    if std::env::args().any(|arg| &arg == "--json") {
+        let status = get_status_json();
+
+        println!("{}", serde_json::to_string_pretty(&status).unwrap());
+
+        return match status["state"].as_str() {
+            Some("launch") => ExitCode::Success,
+            Some(_) => ExitCode::ActionRequired,
+            None => ExitCode::Failure
+        };
+    }
+
+    let Some(state) = super::get_state(false) else {
+        return ExitCode::Failure;
+    };
+
+    println!("Launcher state: {}", super::describe_state(&state));
+
+    print_game_version();
+
+    if let LauncherState::Launch = state {
+        ExitCode::Success
+    } else {
+        ExitCode::ActionRequired
+    }
+}
+
+/// Get launcher status as a JSON object
+///
+/// Errors are reported in the `error` fields of related objects
+/// instead of stopping status gathering
+pub fn get_status_json() -> serde_json::Value {
+    let config = Config::get().unwrap_or_else(|_| CONFIG.clone());
+
+    let (state, state_error) = match super::evaluate_state(false) {
+        Ok(state) => (Some(state), None),
+        Err(err) => {
+            tracing::error!("Failed to update launcher state: {err}");
+
+            (None, Some(err.to_string()))
+        }
+    };
+
+    let missing_fonts = match &state {
+        Some(LauncherState::FontsNotInstalled(fonts)) => fonts.iter()
+            .map(|font| font.name())
+            .collect::<Vec<_>>(),
+
+        _ => Vec::new()
+    };
+
+    // Game version
+
+    let game = get_game_json();
+
+    // Wine and DXVK builds
+
+    let wine = match config.get_selected_wine() {
+        Ok(Some(wine)) => serde_json::json!({
+            "selected": wine.name,
+            "installed": config.game.wine.builds.join(&wine.name).exists()
+        }),
+
+        Ok(None) => serde_json::json!({
+            "selected": null,
+            "installed": false
+        }),
+
+        Err(err) => serde_json::json!({
+            "selected": config.game.wine.selected,
+            "error": err.to_string()
+        })
+    };
+
+    let dxvk = match config.get_selected_dxvk() {
+        Ok(Some(dxvk)) => serde_json::json!({
+            "selected": dxvk.name
+        }),
+
+        Ok(None) => serde_json::json!({
+            "selected": null
+        }),
+
+        Err(err) => serde_json::json!({
+            "selected": null,
+            "error": err.to_string()
+        })
+    };
+
+    // Components index
+
+    let components = get_components_json(&config);
+
+    serde_json::json!({
+        "launcher_version": APP_VERSION,
+        "offline": crate::offline::is_offline(),
+        "state": state.as_ref().map(actions::state_name),
+        "state_error": state_error,
+        "missing_fonts": missing_fonts,
+        "game": game,
+        "game_path": config.game.path,
+        "wine": wine,
+        "dxvk": dxvk,
+        "prefix": {
+            "path": config.game.wine.prefix,
+            "exists": config.game.wine.prefix.exists()
+        },
+        "components": components
+    })
+}
+
+/// Print installed game version and available update
+fn print_game_version() {
+    // Updates can't be checked without the network
+    if crate::offline::is_offline() {
+        match GAME.get_version() {
+            Ok(current) => println!("Game version: {current} (updates can't be checked in offline mode)"),
+            Err(err) => println!("Game version: unknown ({err})")
+        }
+
+        return;
+    }
+
+    match GAME.try_get_diff() {
+        Ok(VersionDiff::Latest(current)) => println!("Game version: {current} (latest)"),
+
+        Ok(VersionDiff::Outdated { current, latest, .. }) => println!("Game version: {current} (update available: {latest})"),
+
+        Ok(VersionDiff::NotInstalled { .. }) => println!("Game version: not installed"),
+
+        Err(err) => {
+            tracing::error!("Failed to find game diff: {err}");
+
+            println!("Game version: unknown ({err})");
+        }
+    }
+}
+
+fn get_game_json() -> serde_json::Value {
+    // Updates can't be checked without the network
+    if crate::offline::is_offline() {
+        return match GAME.get_version() {
+            Ok(current) => serde_json::json!({
+                "diff": null,
+                "current": current.to_string(),
+                "latest": null,
+                "error": "Updates can't be checked in offline mode"
+            }),
+
+            Err(err) => serde_json::json!({
+                "diff": null,
+                "error": err.to_string()
+            })
+        };
+    }
+
+    match GAME.try_get_diff() {
+        Ok(VersionDiff::Latest(current)) => serde_json::json!({
+            "diff": "latest",
+            "current": current.to_string(),
+            "latest": current.to_string()
+        }),
+
+        Ok(VersionDiff::Outdated { current, latest, .. }) => serde_json::json!({
+            "diff": "outdated",
+            "current": current.to_string(),
+            "latest": latest.to_string()
+        }),
+
+        Ok(VersionDiff::NotInstalled { latest, .. }) => serde_json::json!({
+            "diff": "not-installed",
+            "current": null,
+            "latest": latest.to_string()
+        }),
+
+        Err(err) => {
+            tracing::error!("Failed to find game diff: {err}");
+
+            serde_json::json!({
+                "diff": null,
+                "error": err.to_string()
+            })
+        }
+    }
+}
+
+fn get_components_json(config: &Config) -> serde_json::Value {
+    // Components index can't be synced without the network
+    if crate::offline::is_offline() {
+        return serde_json::json!({
+            "path": config.components.path,
+            "synced": null,
+            "error": "Components index can't be checked in offline mode"
+        });
+    }
+
+    match ComponentsLoader::new(&config.components.path).is_sync(&config.components.servers) {
+        Ok(server) => serde_json::json!({
+            "path": config.components.path,
+            "synced": server.is_some(),
+            "server": server
+        }),
+
+        Err(err) => serde_json::json!({
+            "path": config.components.path,
+            "synced": null,
+            "error": err.to_string()
+        })
+    }
+}
@@ -0,0 +1,32 @@
+use anime_launcher_sdk::pgr::states::LauncherState;
+
+use super::ExitCode;
+
+pub fn update() -> ExitCode {
+    let Some(state) = super::get_state(true) else {
+        return ExitCode::Failure;
+    };
+
+    match state {
+        // Local state is always `Launch` if the game is installed
+        LauncherState::Launch if crate::offline::is_offline() => {
+            eprintln!("Game updates can't be checked in offline mode. Run the command without --offline flag");
+
+            ExitCode::Failure
+        }
+
+        LauncherState::Launch => {
+            println!("Game is already updated");
+
+            ExitCode::Success
+        }
+
+        LauncherState::GameUpdateAvailable(_) => super::install::perform(state),
+
+        state => {
+            eprintln!("Can't update the game: {}. Run `install` command first", super::describe_state(&state));
+
+            ExitCode::ActionRequired
+        }
+    }
+}
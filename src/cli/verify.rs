@@ -0,0 +1,85 @@
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::*;
+use crate::actions::DownloadHandle;
+
+use super::ExitCode;
+use super::progress::TerminalProgress;
+use super::install::terminal_updater;
+
+/// Verify game files without repairing them and print the report
+///
+/// Local integrity manifest is used instead of the official integrity files list if given
+pub fn verify(manifest: Option<&Path>) -> ExitCode {
+    let progress = Arc::new(TerminalProgress::default());
+
+    let result = match manifest {
+        Some(manifest) => actions::verify_with_manifest(manifest, &DownloadHandle::default(), terminal_updater(&progress)),
+        None => actions::verify_game(&DownloadHandle::default(), terminal_updater(&progress))
+    };
+
+    progress.finish();
+
+    let report = match result {
+        Ok(Some(report)) => report,
+
+        // Headless verification can't be interrupted
+        Ok(None) => return ExitCode::Failure,
+
+        Err(err) => {
+            eprintln!("{err}");
+
+            return ExitCode::Failure;
+        }
+    };
+
+    if std::env::args().any(|arg| &arg == "--json") {
+        match report.to_json() {
+            Ok(json) => println!("{json}"),
+            Err(err) => {
+                eprintln!("{err}");
+
+                return ExitCode::Failure;
+            }
+        }
+    }
+
+    else {
+        for (title, files) in [
+            ("Mismatched files", &report.mismatched),
+            ("Missing files", &report.missing),
+            ("Extra files", &report.extra),
+            ("Modified excluded files", &report.excluded)
+        ] {
+            println!("{title}: {}", files.len());
+
+            for file in files {
+                println!("  {} ({})", file.path.to_string_lossy(), prettify_bytes(file.actual_size.or(file.expected_size).unwrap_or(0)));
+            }
+        }
+    }
+
+    if report.is_clean() {
+        ExitCode::Success
+    } else {
+        ExitCode::ActionRequired
+    }
+}
+
+/// Save official integrity files list to the local manifest
+pub fn export_manifest(path: &Path) -> ExitCode {
+    match actions::export_manifest(path) {
+        Ok(files) => {
+            println!("Exported {files} files to {}", path.to_string_lossy());
+
+            ExitCode::Success
+        }
+
+        Err(err) => {
+            eprintln!("{err}");
+
+            ExitCode::Failure
+        }
+    }
+}
@@ -0,0 +1,65 @@
+use std::path::PathBuf;
+use std::process::Command;
+
+use crate::network::proxy;
+
+/// Argument used to run the game in a separate launcher process
+///
+/// It's followed by the path to the file where the launching error is written
+pub const GAME_PROCESS_ARG: &str = "--game-process";
+
+/// Run the game and wait until it's closed
+///
+/// The SDK runs wine and the game with the launcher process environment, which contains
+/// proxy variables if the proxy is used (see `network::proxy::apply_from_settings`).
+/// The game shouldn't use the launcher's proxy, so in this case it's run
+/// by a separate launcher process with restored variables
+pub fn run() -> anyhow::Result<()> {
+    if !proxy::is_applied() {
+        return anime_launcher_sdk::pgr::game::run();
+    }
+
+    let error_file = std::env::temp_dir().join(format!("anime-borb-launcher-game-{}.error", std::process::id()));
+
+    let mut command = Command::new(std::env::current_exe()?);
+
+    command.arg(GAME_PROCESS_ARG).arg(&error_file);
+
+    proxy::restore_variables(&mut command);
+
+    let status = command.status()?;
+
+    if status.success() {
+        return Ok(());
+    }
+
+    let error = std::fs::read_to_string(&error_file)
+        .unwrap_or_else(|_| format!("Game process exited with {status}"));
+
+    let _ = std::fs::remove_file(error_file);
+
+    anyhow::bail!(error)
+}
+
+/// Run the game if the launcher process was started by `run`
+///
+/// Returns process exit code
+pub fn run_process() -> Option<i32> {
+    let mut args = std::env::args().skip_while(|arg| arg != GAME_PROCESS_ARG).skip(1);
+
+    let error_file = PathBuf::from(args.next()?);
+
+    match anime_launcher_sdk::pgr::game::run() {
+        Ok(()) => Some(0),
+
+        Err(err) => {
+            eprintln!("Failed to launch game: {err}");
+
+            if let Err(err) = std::fs::write(error_file, err.to_string()) {
+                eprintln!("Failed to write launching error: {err}");
+            }
+
+            Some(1)
+        }
+    }
+}
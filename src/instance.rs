@@ -0,0 +1,153 @@
+use std::time::{Duration, Instant};
+use std::fs::{File, TryLockError};
+
+use gtk::gio;
+use gtk::prelude::*;
+
+use crate::{APP_ID, LOCK_FILE};
+
+/// Argument used by the launcher to restart itself
+///
+/// Restarted instance waits for the previous one to exit instead of forwarding its request to it
+pub const RESTART_ARG: &str = "--restart";
+
+/// How long restarted instance waits for the previous one to exit
+const RESTART_TIMEOUT: Duration = Duration::from_secs(10);
+
+pub enum Instance {
+    /// No launcher window is running
+    ///
+    /// Contains registered GUI application if the launcher window should be opened
+    Primary(Option<adw::Application>),
+
+    /// Another launcher instance is already running
+    Remote(gio::Application)
+}
+
+/// Register launcher on the session bus to find out if another instance is already running
+///
+/// Headless commands never become the primary instance, so they don't block the launcher window.
+/// If session bus is not available the current process is considered the primary one
+pub fn register(headless: bool) -> Instance {
+    let restart = std::env::args().any(|arg| arg == RESTART_ARG);
+    let started = Instant::now();
+
+    loop {
+        let instance = if headless {
+            register_headless()
+        } else {
+            register_gui()
+        };
+
+        match instance {
+            // Wait for the previous instance to exit if we're restarting
+            Instance::Remote(_) if restart && started.elapsed() < RESTART_TIMEOUT => {
+                std::thread::sleep(Duration::from_millis(200));
+            }
+
+            instance => return instance
+        }
+    }
+}
+
+fn register_gui() -> Instance {
+    let app = adw::Application::new(Some(APP_ID), gio::ApplicationFlags::empty());
+
+    // Registration error is checked later using `is_registered`
+    // because logger is not initialized yet
+    if app.register(None::<&gio::Cancellable>).is_ok() && app.is_remote() {
+        Instance::Remote(app.upcast())
+    }
+
+    else {
+        // Present already opened window when another instance forwards its request here
+        let present = gio::SimpleAction::new("present", None);
+
+        present.connect_activate(|_, _| {
+            if let Some(window) = relm4::main_application().active_window() {
+                window.present();
+            }
+        });
+
+        app.add_action(&present);
+
+        Instance::Primary(Some(app))
+    }
+}
+
+fn register_headless() -> Instance {
+    let app = gio::Application::new(Some(APP_ID), gio::ApplicationFlags::IS_LAUNCHER);
+
+    match app.register(None::<&gio::Cancellable>) {
+        // With `IS_LAUNCHER` flag application is always remote,
+        // so check if there's the launcher window which can handle our requests
+        Ok(_) if app.has_action("present") => Instance::Remote(app),
+
+        _ => Instance::Primary(None)
+    }
+}
+
+/// Forward request to the primary instance
+///
+/// If `run_game` is true, then primary instance will launch the game if it's ready to,
+/// or present its window otherwise
+pub fn forward(app: &gio::Application, run_game: bool) -> anyhow::Result<()> {
+    let action = if run_game && app.has_action("run-game") {
+        "run-game"
+    } else {
+        "present"
+    };
+
+    if !app.has_action(action) {
+        anyhow::bail!("Primary launcher instance doesn't handle forwarded requests");
+    }
+
+    tracing::info!("Forwarding '{action}' request to the running launcher instance");
+
+    app.activate_action(action, None);
+
+    // Make sure the request was sent before we exit
+    if let Some(connection) = app.dbus_connection() {
+        connection.flush_sync(None::<&gio::Cancellable>)?;
+    }
+
+    Ok(())
+}
+
+/// Exclusive lock of the launcher's config and debug log
+///
+/// Only the process holding this lock can modify launcher files.
+/// Lock is released when the process exits
+pub struct FilesLock {
+    _file: File
+}
+
+impl FilesLock {
+    /// Take the lock of the launcher files
+    ///
+    /// Returns `Ok(None)` if another launcher process holds the lock.
+    /// Restarted instance waits for the previous one to release it
+    pub fn take() -> std::io::Result<Option<Self>> {
+        let restart = std::env::args().any(|arg| arg == RESTART_ARG);
+        let started = Instant::now();
+
+        let file = File::options()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(LOCK_FILE.as_path())?;
+
+        loop {
+            match file.try_lock() {
+                Ok(()) => return Ok(Some(Self { _file: file })),
+
+                Err(TryLockError::WouldBlock) if restart && started.elapsed() < RESTART_TIMEOUT => {
+                    std::thread::sleep(Duration::from_millis(200));
+                }
+
+                Err(TryLockError::WouldBlock) => return Ok(None),
+                Err(TryLockError::Error(err)) => return Err(err)
+            }
+        }
+    }
+}
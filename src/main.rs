@@ -11,17 +11,28 @@ use anime_launcher_sdk::anime_game_core::pgr::prelude::*;
 
 use tracing_subscriber::prelude::*;
 use tracing_subscriber::filter::*;
+use tracing_subscriber::fmt::writer::BoxMakeWriter;
 
 use std::path::PathBuf;
 
 pub mod move_folder;
 pub mod i18n;
 pub mod background;
+pub mod settings;
+pub mod network;
+pub mod offline;
+pub mod checklist;
+pub mod instance;
+pub mod game;
+pub mod actions;
+pub mod cli;
 pub mod ui;
 
 use ui::main::*;
 use ui::first_run::main::*;
 
+use instance::Instance;
+
 pub const APP_ID: &str = "moe.launcher.an-anime-borb-launcher";
 pub const APP_VERSION: &str = env!("CARGO_PKG_VERSION");
 pub const APP_DEBUG: bool = cfg!(debug_assertions);
@@ -53,6 +64,39 @@ lazy_static::lazy_static! {
     /// Path to `debug.log` file. Standard is `$HOME/.local/share/anime-borb-launcher/debug.log`
     pub static ref DEBUG_FILE: PathBuf = LAUNCHER_FOLDER.join("debug.log");
 
+    /// Path to `settings.json` file. Standard is `$HOME/.local/share/anime-borb-launcher/settings.json`
+    pub static ref SETTINGS_FILE: PathBuf = LAUNCHER_FOLDER.join("settings.json");
+
+    /// Path to `updates.log` file. Contains automatic game updates history
+    ///
+    /// Standard is `$HOME/.local/share/anime-borb-launcher/updates.log`
+    pub static ref UPDATES_LOG_FILE: PathBuf = LAUNCHER_FOLDER.join("updates.log");
+
+    /// Path to `.last-update-check` file. Contains unix timestamp of the last automatic game updates check
+    ///
+    /// Standard is `$HOME/.local/share/anime-borb-launcher/.last-update-check`
+    pub static ref LAST_UPDATE_CHECK_FILE: PathBuf = LAUNCHER_FOLDER.join(".last-update-check");
+
+    /// Path to `.recent-download-speed` file. Contains average speed of the last finished download
+    ///
+    /// Standard is `$HOME/.local/share/anime-borb-launcher/.recent-download-speed`
+    pub static ref RECENT_SPEED_FILE: PathBuf = LAUNCHER_FOLDER.join(".recent-download-speed");
+
+    /// Path to `.integrity-cache.json` file. Contains metadata of the verified game files
+    ///
+    /// Standard is `$HOME/.local/share/anime-borb-launcher/.integrity-cache.json`
+    pub static ref INTEGRITY_CACHE_FILE: PathBuf = LAUNCHER_FOLDER.join(".integrity-cache.json");
+
+    /// Path to `.lock` file. Locked by the launcher process which owns config and debug.log
+    ///
+    /// Standard is `$HOME/.local/share/anime-borb-launcher/.lock`
+    pub static ref LOCK_FILE: PathBuf = LAUNCHER_FOLDER.join(".lock");
+
+    /// Path to `.expected-archive.json` file. Contains game archive required by the last game version diff found online
+    ///
+    /// Standard is `$HOME/.local/share/anime-borb-launcher/.expected-archive.json`
+    pub static ref EXPECTED_ARCHIVE_FILE: PathBuf = LAUNCHER_FOLDER.join(".expected-archive.json");
+
     /// Path to `background` file. Standard is `$HOME/.cache/anime-borb-launcher/background`
     pub static ref BACKGROUND_FILE: PathBuf = CACHE_FOLDER.join("background");
 
@@ -71,10 +115,110 @@ lazy_static::lazy_static! {
 }
 
 fn main() {
-    // Create launcher folder if it isn't
-    if !LAUNCHER_FOLDER.exists() {
+    // Run the game in this process if it was started by another launcher process
+    if let Some(code) = game::run_process() {
+        std::process::exit(code);
+    }
+
+    // Create launcher folder if it isn't. Launcher files are
+    // prepared later, when this process holds the files lock
+    let first_run = !LAUNCHER_FOLDER.exists();
+
+    if first_run {
         std::fs::create_dir_all(LAUNCHER_FOLDER.as_path()).expect("Failed to create launcher folder");
+    }
+
+    // Route launcher network traffic through the proxy if it's set.
+    // Must be done before GTK and session bus spawn their threads
+    let proxy = network::proxy::apply_from_settings();
+
+    // Force debug output
+    let force_debug = std::env::args().any(|arg| &arg == "--debug");
+
+    // Run the game
+    let run_game = std::env::args().any(|arg| &arg == "--run-game");
+
+    // Forcely run the game
+    let just_run_game = std::env::args().any(|arg| &arg == "--just-run-game");
+
+    // Don't use the network
+    if std::env::args().any(|arg| &arg == "--offline") {
+        offline::set_offline(true);
+    }
+
+    // Run headless command instead of the GUI
+    let command = match cli::Command::from_args() {
+        Some(Ok(command)) => Some(command),
+        Some(Err(err)) => {
+            eprintln!("{err}");
+
+            cli::print_usage();
+
+            std::process::exit(cli::ExitCode::Usage as i32);
+        }
+
+        None => None
+    };
+
+    // GTK is needed to register launcher window's application
+    if command.is_none() {
+        adw::init().expect("Libadwaita initialization failed");
+    }
+
+    // Check if another launcher instance is already running
+    let instance = instance::register(command.is_some());
+
+    if let Instance::Remote(app) = &instance {
+        match command {
+            // Forward request to the running instance
+            None | Some(cli::Command::Launch) => {
+                if let Err(err) = instance::forward(app, run_game || just_run_game || command.is_some()) {
+                    eprintln!("Failed to forward request to the running launcher: {err}");
+
+                    std::process::exit(cli::ExitCode::AlreadyRunning as i32);
+                }
+
+                std::process::exit(cli::ExitCode::Success as i32);
+            }
+
+            // Read-only commands can be run alongside the launcher window
+            Some(command) if command.is_read_only() => (),
+
+            Some(_) => {
+                eprintln!("Launcher is already running. Close it to run this command");
+
+                std::process::exit(cli::ExitCode::AlreadyRunning as i32);
+            }
+        }
+    }
+
+    // Only one process can modify launcher files, so the launcher window
+    // and headless commands don't overwrite config and truncate debug.log of each other.
+    // Read-only commands take the lock only on the first run to prepare launcher files
+    let owns_files = first_run || !command.as_ref().is_some_and(|command| command.is_read_only());
+
+    let _files_lock = if owns_files {
+        match instance::FilesLock::take() {
+            Ok(Some(lock)) => Some(lock),
+
+            Ok(None) => {
+                eprintln!("Launcher files are used by another launcher process. Wait until it's finished");
+
+                std::process::exit(cli::ExitCode::AlreadyRunning as i32);
+            }
 
+            // Lock could be not supported by the filesystem
+            Err(err) => {
+                eprintln!("Failed to lock launcher files: {err}");
+
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    if first_run {
         // This one is kinda critical buy well, I can't do something with it
         std::fs::write(FIRST_RUN_FILE.as_path(), "").expect("Failed to create .first-run file");
 
@@ -87,18 +231,17 @@ fn main() {
         Config::update_raw(config).expect("Failed to update config");
     }
 
-    // Force debug output
-    let force_debug = std::env::args().any(|arg| &arg == "--debug");
-
-    // Run the game
-    let run_game = std::env::args().any(|arg| &arg == "--run-game");
-
-    // Forcely run the game
-    let just_run_game = std::env::args().any(|arg| &arg == "--just-run-game");
-
     // Prepare stdout logger
     let stdout = tracing_subscriber::fmt::layer()
         .pretty()
+        .with_writer({
+            // Keep stdout clean for headless commands' output
+            if command.is_some() {
+                BoxMakeWriter::new(std::io::stderr)
+            } else {
+                BoxMakeWriter::new(std::io::stdout)
+            }
+        })
         .with_filter({
             if APP_DEBUG || force_debug {
                 LevelFilter::TRACE
@@ -112,19 +255,22 @@ fn main() {
         }));
 
     // Prepare debug file logger
-    let file = match std::fs::File::create(DEBUG_FILE.as_path()) {
-        Ok(file) => file,
-        Err(error) => panic!("Failed to create debug.log file: {:?}", error)
-    };
-
-    let debug_log = tracing_subscriber::fmt::layer()
-        .pretty()
-        .with_ansi(false)
-        .with_writer(std::sync::Arc::new(file))
-        .with_filter(filter_fn(|metadata| {
-            !metadata.target().contains("rustls") &&
-            !metadata.target().contains("minreq")
-        }));
+    // Only files owner writes debug.log so we don't truncate it under another launcher process
+    let debug_log = owns_files.then(|| {
+        let file = match std::fs::File::create(DEBUG_FILE.as_path()) {
+            Ok(file) => file,
+            Err(error) => panic!("Failed to create debug.log file: {:?}", error)
+        };
+
+        tracing_subscriber::fmt::layer()
+            .pretty()
+            .with_ansi(false)
+            .with_writer(std::sync::Arc::new(file))
+            .with_filter(filter_fn(|metadata| {
+                !metadata.target().contains("rustls") &&
+                !metadata.target().contains("minreq")
+            }))
+    });
 
     tracing_subscriber::registry()
         .with(stdout)
@@ -133,7 +279,34 @@ fn main() {
 
     tracing::info!("Starting application ({APP_VERSION})");
 
-    adw::init().expect("Libadwaita initialization failed");
+    if let Instance::Primary(Some(app)) = &instance {
+        if !app.is_registered() {
+            tracing::warn!("Failed to register application on the session bus. Single instance mode is disabled");
+        }
+    }
+
+    // Set UI language
+    let lang = CONFIG.launcher.language.parse().expect("Wrong language format used in config");
+
+    i18n::set_lang(lang).expect("Failed to set launcher language");
+
+    tracing::info!("Set UI language to {}", i18n::get_lang());
+
+    match proxy {
+        Ok(Some(proxy)) => tracing::info!("Using {:?} proxy: {}:{}", proxy.kind, proxy.host, proxy.port),
+        Err(err) => tracing::error!("Failed to apply proxy from launcher settings: {err}"),
+
+        Ok(None) => ()
+    }
+
+    // Run headless command without initializing GTK
+    if let Some(command) = command {
+        std::process::exit(cli::run(command) as i32);
+    }
+
+    let Instance::Primary(Some(app)) = instance else {
+        unreachable!("Launcher window can be opened only by the primary instance");
+    };
 
     // Register and include resources
     gtk::gio::resources_register_include!("resources.gresource")
@@ -173,17 +346,10 @@ fn main() {
         }}
     ", BACKGROUND_FILE.to_string_lossy()));
 
-    // Set UI language
-    let lang = CONFIG.launcher.language.parse().expect("Wrong language format used in config");
-
-    i18n::set_lang(lang).expect("Failed to set launcher language");
-
-    tracing::info!("Set UI language to {}", i18n::get_lang());
-
     // Run FirstRun window if .first-run file persist
     if FIRST_RUN_FILE.exists() {
         // Create the app
-        let app = RelmApp::new(APP_ID);
+        let app = RelmApp::from_app(app);
 
         // Show first run window
         app.run::<FirstRunApp>(());
@@ -196,14 +362,14 @@ fn main() {
                 .expect("Failed to get launcher state");
 
             if let LauncherState::Launch = state {
-                anime_launcher_sdk::pgr::game::run().expect("Failed to run the game");
+                game::run().expect("Failed to run the game");
 
                 return;
             }
         }
 
         // Create the app
-        let app = RelmApp::new(APP_ID);
+        let app = RelmApp::from_app(app);
 
         // Show main window
         app.run::<App>(());
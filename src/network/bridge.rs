@@ -0,0 +1,639 @@
+use std::io::{self, Read, Write};
+use std::net::{TcpListener, TcpStream, SocketAddr, IpAddr, Shutdown, ToSocketAddrs};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use crate::settings::{Proxy, ProxyKind};
+
+/// Timeout of TCP connections to the proxy and the requested servers
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Timeout of reading requests and proxy handshakes
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Max length of the request or proxy response head
+const MAX_HEAD_LENGTH: usize = 64 * 1024;
+
+/// Username which clients use to authorize in the bridge
+const BRIDGE_USERNAME: &str = "launcher";
+
+/// Local HTTP proxy which forwards launcher connections to the proxy from the launcher settings
+///
+/// Network clients used by the launcher (`minreq` in the SDK and the launcher, `git` for components sync)
+/// support different proxy types, and only through the URL in the environment variables. The bridge gives
+/// them a plain HTTP proxy on the loopback interface, so SOCKS5 proxies work for all of them and the real
+/// proxy credentials never appear in the environment. Clients authorize with a random token instead,
+/// so other local users can't use the bridge
+pub struct Bridge {
+    listener: TcpListener,
+    upstream: Option<Proxy>,
+    token: String
+}
+
+impl Bridge {
+    /// Bind bridge to a random local port
+    ///
+    /// Connections are made directly if `upstream` is `None`
+    pub fn bind(upstream: Option<Proxy>) -> io::Result<Self> {
+        Ok(Self {
+            listener: TcpListener::bind("127.0.0.1:0")?,
+            upstream,
+            token: random_token()?
+        })
+    }
+
+    /// Get bridge proxy URL with the authorization token
+    pub fn url(&self) -> io::Result<String> {
+        Ok(format!("http://{BRIDGE_USERNAME}:{}@{}", self.token, self.listener.local_addr()?))
+    }
+
+    /// Start accepting connections in a background thread
+    ///
+    /// Bridge is stopped when returned handle is dropped
+    pub fn spawn(self) -> io::Result<RunningBridge> {
+        let running = RunningBridge {
+            url: self.url()?,
+            addr: self.listener.local_addr()?,
+            stopped: Arc::new(AtomicBool::new(false))
+        };
+
+        let stopped = running.stopped.clone();
+
+        std::thread::spawn(move || {
+            let upstream = Arc::new(self.upstream);
+            let authorization = Arc::new(format!("Basic {}", base64(format!("{BRIDGE_USERNAME}:{}", self.token).as_bytes())));
+
+            for client in self.listener.incoming() {
+                if stopped.load(Ordering::Relaxed) {
+                    break;
+                }
+
+                let Ok(client) = client else {
+                    continue;
+                };
+
+                let upstream = upstream.clone();
+                let authorization = authorization.clone();
+
+                std::thread::spawn(move || {
+                    if let Err(err) = serve(client, upstream.as_ref().as_ref(), &authorization) {
+                        tracing::warn!("Proxy connection failed: {err}");
+                    }
+                });
+            }
+        });
+
+        Ok(running)
+    }
+}
+
+/// Handle of the bridge accepting connections
+pub struct RunningBridge {
+    url: String,
+    addr: SocketAddr,
+    stopped: Arc<AtomicBool>
+}
+
+impl RunningBridge {
+    /// Get bridge proxy URL with the authorization token
+    #[inline]
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+}
+
+impl Drop for RunningBridge {
+    fn drop(&mut self) {
+        self.stopped.store(true, Ordering::Relaxed);
+
+        // Wake up the listener so it sees that it was stopped
+        let _ = TcpStream::connect_timeout(&self.addr, CONNECT_TIMEOUT);
+    }
+}
+
+/// Serve single client connection
+///
+/// `CONNECT` requests are tunneled as is, and plain HTTP requests are forwarded to their servers
+fn serve(mut client: TcpStream, upstream: Option<&Proxy>, authorization: &str) -> io::Result<()> {
+    client.set_read_timeout(Some(HANDSHAKE_TIMEOUT))?;
+
+    let head = read_head(&mut client)?;
+
+    let mut lines = head.split("\r\n");
+
+    let mut request_line = lines.next().unwrap_or_default().split(' ');
+
+    let (Some(method), Some(target), Some(version)) = (request_line.next(), request_line.next(), request_line.next()) else {
+        client.write_all(b"HTTP/1.1 400 Bad Request\r\nContent-Length: 0\r\nConnection: close\r\n\r\n")?;
+
+        return Err(io::Error::other("malformed request"));
+    };
+
+    let headers = lines.filter(|line| !line.is_empty())
+        .filter_map(|line| line.split_once(':'))
+        .map(|(name, value)| (name.trim(), value.trim()))
+        .collect::<Vec<_>>();
+
+    let authorized = headers.iter()
+        .any(|(name, value)| name.eq_ignore_ascii_case("proxy-authorization") && *value == authorization);
+
+    if !authorized {
+        client.write_all(b"HTTP/1.1 407 Proxy Authentication Required\r\nProxy-Authenticate: Basic realm=\"launcher\"\r\nContent-Length: 0\r\nConnection: close\r\n\r\n")?;
+
+        return Ok(());
+    }
+
+    let server = if method == "CONNECT" {
+        let (host, port) = split_host_port(target, 443)?;
+
+        let server = connect_or_respond(&mut client, upstream, host, port)?;
+
+        client.write_all(b"HTTP/1.1 200 Connection established\r\n\r\n")?;
+
+        server
+    }
+
+    else {
+        let Some(url) = target.strip_prefix("http://") else {
+            client.write_all(b"HTTP/1.1 400 Bad Request\r\nContent-Length: 0\r\nConnection: close\r\n\r\n")?;
+
+            return Err(io::Error::other(format!("unsupported request target: {target}")));
+        };
+
+        let (authority, path) = match url.find('/') {
+            Some(pos) => url.split_at(pos),
+            None => (url, "/")
+        };
+
+        let (host, port) = split_host_port(authority, 80)?;
+
+        let mut server = connect_or_respond(&mut client, upstream, host, port)?;
+
+        // Server gets the request in origin form and without proxy headers.
+        // Connection is closed after the response because it's tied to this server
+        let mut request = format!("{method} {path} {version}\r\n");
+
+        for (name, value) in headers {
+            if !name.to_ascii_lowercase().starts_with("proxy-") && !name.eq_ignore_ascii_case("connection") {
+                request += &format!("{name}: {value}\r\n");
+            }
+        }
+
+        request += "Connection: close\r\n\r\n";
+
+        server.write_all(request.as_bytes())?;
+
+        server
+    };
+
+    client.set_read_timeout(None)?;
+
+    pipe(client, server)
+}
+
+/// Connect to the server or tell the client that it's unavailable
+fn connect_or_respond(client: &mut TcpStream, upstream: Option<&Proxy>, host: &str, port: u16) -> io::Result<TcpStream> {
+    match connect(upstream, host, port) {
+        Ok(server) => Ok(server),
+
+        Err(err) => {
+            client.write_all(b"HTTP/1.1 502 Bad Gateway\r\nContent-Length: 0\r\nConnection: close\r\n\r\n")?;
+
+            Err(io::Error::new(err.kind(), format!("failed to connect to {host}:{port}: {err}")))
+        }
+    }
+}
+
+/// Copy data between the client and the server until one of them closes the connection
+fn pipe(client: TcpStream, server: TcpStream) -> io::Result<()> {
+    let mut client_reader = client.try_clone()?;
+    let mut server_writer = server.try_clone()?;
+
+    let upload = std::thread::spawn(move || {
+        let _ = io::copy(&mut client_reader, &mut server_writer);
+        let _ = server_writer.shutdown(Shutdown::Write);
+    });
+
+    let _ = io::copy(&mut &server, &mut &client);
+
+    let _ = client.shutdown(Shutdown::Both);
+    let _ = server.shutdown(Shutdown::Both);
+
+    let _ = upload.join();
+
+    Ok(())
+}
+
+/// Open connection to the server through the upstream proxy
+fn connect(upstream: Option<&Proxy>, host: &str, port: u16) -> io::Result<TcpStream> {
+    let Some(proxy) = upstream else {
+        return connect_tcp(host, port);
+    };
+
+    let mut stream = connect_tcp(&proxy.host, proxy.port)?;
+
+    stream.set_read_timeout(Some(HANDSHAKE_TIMEOUT))?;
+
+    match proxy.kind {
+        ProxyKind::Http => http_connect(&mut stream, proxy, host, port)?,
+        ProxyKind::Socks5 => socks5_connect(&mut stream, proxy, host, port)?
+    }
+
+    stream.set_read_timeout(None)?;
+
+    Ok(stream)
+}
+
+fn connect_tcp(host: &str, port: u16) -> io::Result<TcpStream> {
+    let mut last_error = None;
+
+    for addr in (host, port).to_socket_addrs()? {
+        match TcpStream::connect_timeout(&addr, CONNECT_TIMEOUT) {
+            Ok(stream) => return Ok(stream),
+            Err(err) => last_error = Some(err)
+        }
+    }
+
+    Err(last_error.unwrap_or_else(|| io::Error::new(io::ErrorKind::NotFound, format!("{host} is not resolved"))))
+}
+
+/// Open tunnel using HTTP `CONNECT` request
+fn http_connect(stream: &mut TcpStream, proxy: &Proxy, host: &str, port: u16) -> io::Result<()> {
+    let authority = if host.contains(':') {
+        format!("[{host}]:{port}")
+    } else {
+        format!("{host}:{port}")
+    };
+
+    let mut request = format!("CONNECT {authority} HTTP/1.1\r\nHost: {authority}\r\n");
+
+    if !proxy.username.is_empty() {
+        request += &format!("Proxy-Authorization: Basic {}\r\n", base64(format!("{}:{}", proxy.username, proxy.password).as_bytes()));
+    }
+
+    request += "\r\n";
+
+    stream.write_all(request.as_bytes())?;
+
+    let head = read_head(stream)?;
+
+    let status = head.split(' ').nth(1).unwrap_or_default();
+
+    match status {
+        "407" => Err(io::Error::new(io::ErrorKind::PermissionDenied, "proxy authorization failed")),
+
+        _ if status.starts_with('2') => Ok(()),
+
+        _ => Err(io::Error::other(format!("proxy responded with {status} status code")))
+    }
+}
+
+/// Open tunnel using SOCKS5 protocol (RFC 1928) with optional username and password authorization (RFC 1929)
+///
+/// Domain names are resolved by the proxy
+fn socks5_connect(stream: &mut TcpStream, proxy: &Proxy, host: &str, port: u16) -> io::Result<()> {
+    let authorize = !proxy.username.is_empty();
+
+    if authorize {
+        stream.write_all(&[5, 2, 0, 2])?;
+    } else {
+        stream.write_all(&[5, 1, 0])?;
+    }
+
+    let mut reply = [0; 2];
+
+    stream.read_exact(&mut reply)?;
+
+    if reply[0] != 5 {
+        return Err(io::Error::other("proxy server doesn't support SOCKS5 protocol"));
+    }
+
+    match reply[1] {
+        0 => (),
+
+        2 if authorize => {
+            if proxy.username.len() > 255 || proxy.password.len() > 255 {
+                return Err(io::Error::new(io::ErrorKind::InvalidInput, "proxy username and password must be shorter than 256 bytes"));
+            }
+
+            let mut request = vec![1, proxy.username.len() as u8];
+
+            request.extend_from_slice(proxy.username.as_bytes());
+            request.push(proxy.password.len() as u8);
+            request.extend_from_slice(proxy.password.as_bytes());
+
+            stream.write_all(&request)?;
+            stream.read_exact(&mut reply)?;
+
+            if reply[1] != 0 {
+                return Err(io::Error::new(io::ErrorKind::PermissionDenied, "proxy authorization failed"));
+            }
+        }
+
+        _ => return Err(io::Error::new(io::ErrorKind::PermissionDenied, "proxy requires unsupported authorization method"))
+    }
+
+    let mut request = vec![5, 1, 0];
+
+    match host.parse::<IpAddr>() {
+        Ok(IpAddr::V4(ip)) => {
+            request.push(1);
+            request.extend_from_slice(&ip.octets());
+        }
+
+        Ok(IpAddr::V6(ip)) => {
+            request.push(4);
+            request.extend_from_slice(&ip.octets());
+        }
+
+        Err(_) if host.len() <= 255 => {
+            request.push(3);
+            request.push(host.len() as u8);
+            request.extend_from_slice(host.as_bytes());
+        }
+
+        Err(_) => return Err(io::Error::new(io::ErrorKind::InvalidInput, "host name is too long"))
+    }
+
+    request.extend_from_slice(&port.to_be_bytes());
+
+    stream.write_all(&request)?;
+
+    let mut reply = [0; 4];
+
+    stream.read_exact(&mut reply)?;
+
+    if reply[1] != 0 {
+        return Err(io::Error::other(format!("proxy responded with {} error code", reply[1])));
+    }
+
+    // Skip address bound by the proxy
+    let address_length = match reply[3] {
+        1 => 4,
+        4 => 16,
+
+        3 => {
+            let mut length = [0];
+
+            stream.read_exact(&mut length)?;
+
+            length[0] as usize
+        }
+
+        _ => return Err(io::Error::other("proxy responded with unknown address type"))
+    };
+
+    stream.read_exact(&mut vec![0; address_length + 2])
+}
+
+/// Read HTTP request or response head
+///
+/// Head is read byte by byte so the data sent after it stays in the stream
+fn read_head(stream: &mut TcpStream) -> io::Result<String> {
+    let mut head = Vec::new();
+    let mut byte = [0];
+
+    while !head.ends_with(b"\r\n\r\n") {
+        if head.len() >= MAX_HEAD_LENGTH {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "HTTP head is too long"));
+        }
+
+        stream.read_exact(&mut byte)?;
+
+        head.push(byte[0]);
+    }
+
+    String::from_utf8(head).map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "HTTP head is not valid UTF-8"))
+}
+
+/// Split `host:port` authority, with optional port and IPv6 hosts in square brackets
+fn split_host_port(authority: &str, default_port: u16) -> io::Result<(&str, u16)> {
+    let (host, port) = match authority.rsplit_once(':') {
+        Some((host, port)) if !port.contains(']') => {
+            let port = port.parse().map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, format!("wrong port in {authority}")))?;
+
+            (host, port)
+        }
+
+        _ => (authority, default_port)
+    };
+
+    Ok((host.trim_start_matches('[').trim_end_matches(']'), port))
+}
+
+/// Generate random token for the bridge authorization
+fn random_token() -> io::Result<String> {
+    let mut bytes = [0; 16];
+
+    std::fs::File::open("/dev/urandom")?.read_exact(&mut bytes)?;
+
+    Ok(bytes.iter().map(|byte| format!("{byte:02x}")).collect())
+}
+
+fn base64(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut result = String::with_capacity(data.len().div_ceil(3) * 4);
+
+    for chunk in data.chunks(3) {
+        let bytes = [chunk[0], chunk.get(1).copied().unwrap_or(0), chunk.get(2).copied().unwrap_or(0)];
+        let value = u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]]);
+
+        for i in 0..4 {
+            if i <= chunk.len() {
+                result.push(ALPHABET[(value >> (18 - i * 6)) as usize & 63] as char);
+            } else {
+                result.push('=');
+            }
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::BufRead;
+
+    use super::*;
+
+    /// Start server which responds with "hello" to every connection and returns the received request line
+    fn serve_hello() -> (SocketAddr, std::sync::mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let (sender, receiver) = std::sync::mpsc::channel();
+
+        std::thread::spawn(move || {
+            for mut stream in listener.incoming().flatten() {
+                let mut line = String::new();
+
+                io::BufReader::new(&stream).read_line(&mut line).unwrap();
+
+                sender.send(line.trim().to_string()).unwrap();
+
+                stream.write_all(b"hello").unwrap();
+            }
+        });
+
+        (addr, receiver)
+    }
+
+    /// Start SOCKS5 proxy which accepts only `user:pass` and returns requested domain names
+    fn serve_socks5() -> (u16, std::sync::mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        let (sender, receiver) = std::sync::mpsc::channel();
+
+        std::thread::spawn(move || {
+            for mut client in listener.incoming().flatten() {
+                let mut greeting = [0; 4];
+
+                client.read_exact(&mut greeting).unwrap();
+
+                assert_eq!(greeting, [5, 2, 0, 2]);
+
+                client.write_all(&[5, 2]).unwrap();
+
+                let mut auth = [0; 11];
+
+                client.read_exact(&mut auth).unwrap();
+
+                assert_eq!(&auth, b"\x01\x04user\x04pass");
+
+                client.write_all(&[1, 0]).unwrap();
+
+                let mut request = [0; 5];
+
+                client.read_exact(&mut request).unwrap();
+
+                assert_eq!(request[..4], [5, 1, 0, 3]);
+
+                let mut domain = vec![0; request[4] as usize + 2];
+
+                client.read_exact(&mut domain).unwrap();
+
+                let port = u16::from_be_bytes([domain[domain.len() - 2], domain[domain.len() - 1]]);
+
+                sender.send(format!("{}:{port}", String::from_utf8_lossy(&domain[..domain.len() - 2]))).unwrap();
+
+                client.write_all(&[5, 0, 0, 1, 127, 0, 0, 1, 0, 0]).unwrap();
+                client.write_all(b"tunneled").unwrap();
+            }
+        });
+
+        (port, receiver)
+    }
+
+    fn request(bridge: &RunningBridge, request: &str) -> String {
+        let addr = bridge.url().rsplit_once('@').unwrap().1;
+
+        let mut stream = TcpStream::connect(addr).unwrap();
+
+        stream.write_all(request.as_bytes()).unwrap();
+
+        let mut response = String::new();
+
+        stream.read_to_string(&mut response).unwrap();
+
+        response
+    }
+
+    fn authorization(bridge: &RunningBridge) -> String {
+        let credentials = bridge.url().strip_prefix("http://").unwrap().rsplit_once('@').unwrap().0;
+
+        format!("Proxy-Authorization: Basic {}\r\n", base64(credentials.as_bytes()))
+    }
+
+    #[test]
+    fn encodes_base64() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foo"), "Zm9v");
+        assert_eq!(base64(b"user:p@ss"), "dXNlcjpwQHNz");
+    }
+
+    #[test]
+    fn splits_host_and_port() {
+        assert_eq!(split_host_port("example.com:8080", 80).unwrap(), ("example.com", 8080));
+        assert_eq!(split_host_port("example.com", 80).unwrap(), ("example.com", 80));
+        assert_eq!(split_host_port("[::1]:443", 80).unwrap(), ("::1", 443));
+        assert_eq!(split_host_port("[::1]", 80).unwrap(), ("::1", 80));
+    }
+
+    #[test]
+    fn requires_authorization() {
+        let bridge = Bridge::bind(None).unwrap().spawn().unwrap();
+
+        let response = request(&bridge, "CONNECT example.com:443 HTTP/1.1\r\nHost: example.com:443\r\n\r\n");
+
+        assert!(response.starts_with("HTTP/1.1 407"));
+    }
+
+    #[test]
+    fn tunnels_connect_requests() {
+        let (server, requests) = serve_hello();
+        let bridge = Bridge::bind(None).unwrap().spawn().unwrap();
+
+        let response = request(&bridge, &format!("CONNECT {server} HTTP/1.1\r\n{}\r\nping\r\n", authorization(&bridge)));
+
+        assert_eq!(response, "HTTP/1.1 200 Connection established\r\n\r\nhello");
+        assert_eq!(requests.recv().unwrap(), "ping");
+    }
+
+    #[test]
+    fn forwards_plain_requests_in_origin_form() {
+        let (server, requests) = serve_hello();
+        let bridge = Bridge::bind(None).unwrap().spawn().unwrap();
+
+        let response = request(&bridge, &format!("GET http://{server}/index.html HTTP/1.1\r\nHost: {server}\r\n{}\r\n", authorization(&bridge)));
+
+        assert_eq!(response, "hello");
+        assert_eq!(requests.recv().unwrap(), "GET /index.html HTTP/1.1");
+    }
+
+    #[test]
+    fn tunnels_through_http_proxy() {
+        let (upstream, requests) = serve_hello();
+
+        let proxy = Proxy {
+            enabled: true,
+            host: upstream.ip().to_string(),
+            port: upstream.port(),
+            ..Proxy::default()
+        };
+
+        // Upstream responds with "hello" instead of HTTP status, so the tunnel is not opened
+        let bridge = Bridge::bind(Some(proxy)).unwrap().spawn().unwrap();
+
+        let response = request(&bridge, &format!("CONNECT example.com:443 HTTP/1.1\r\n{}\r\n", authorization(&bridge)));
+
+        assert!(response.starts_with("HTTP/1.1 502"));
+        assert_eq!(requests.recv().unwrap(), "CONNECT example.com:443 HTTP/1.1");
+    }
+
+    #[test]
+    fn tunnels_through_socks5_proxy() {
+        let (port, requests) = serve_socks5();
+
+        let proxy = Proxy {
+            enabled: true,
+            kind: ProxyKind::Socks5,
+            host: String::from("127.0.0.1"),
+            port,
+            username: String::from("user"),
+            password: String::from("pass"),
+            ..Proxy::default()
+        };
+
+        let bridge = Bridge::bind(Some(proxy)).unwrap().spawn().unwrap();
+
+        let response = request(&bridge, &format!("CONNECT example.com:443 HTTP/1.1\r\n{}\r\n", authorization(&bridge)));
+
+        assert_eq!(response, "HTTP/1.1 200 Connection established\r\n\r\ntunneled");
+        assert_eq!(requests.recv().unwrap(), "example.com:443");
+    }
+}
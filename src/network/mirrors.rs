@@ -0,0 +1,37 @@
+use std::time::{Duration, Instant};
+
+use anime_launcher_sdk::anime_game_core::minreq;
+
+/// Timeout used to measure mirrors latency
+const LATENCY_TIMEOUT: u64 = 5;
+
+/// Maximal delay between download attempts
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+/// Get file's URL on the mirror
+///
+/// Mirrors are plain folders with the game archives served over HTTP,
+/// so e.g. `python -m http.server` can be used as a mirror
+pub fn mirror_url(base: &str, file_name: &str) -> String {
+    format!("{}/{file_name}", base.trim_end_matches('/'))
+}
+
+/// Measure mirror response time
+pub fn latency(base: &str) -> anyhow::Result<Duration> {
+    let started = Instant::now();
+
+    let response = minreq::head(base)
+        .with_timeout(LATENCY_TIMEOUT)
+        .send()?;
+
+    if response.status_code >= 500 {
+        anyhow::bail!("{} {}", response.status_code, response.reason_phrase);
+    }
+
+    Ok(started.elapsed())
+}
+
+/// Delay before the next download attempt: 2, 4, 8... seconds
+pub fn backoff(attempt: u32) -> Duration {
+    Duration::from_secs(2u64.saturating_pow(attempt + 1)).min(MAX_BACKOFF)
+}
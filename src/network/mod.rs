@@ -0,0 +1,8 @@
+pub mod throttle;
+pub mod speed;
+pub mod mirrors;
+pub mod proxy;
+pub mod bridge;
+
+pub use throttle::{throttle_installer, throttle_downloader};
+pub use speed::SpeedMeter;
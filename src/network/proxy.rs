@@ -0,0 +1,134 @@
+use std::ffi::OsString;
+use std::process::Command;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+use anime_launcher_sdk::anime_game_core::minreq;
+
+use crate::settings::{Settings, Proxy};
+
+use super::bridge::{Bridge, RunningBridge};
+
+/// Timeout used to test proxy connection
+const TEST_TIMEOUT: u64 = 10;
+
+/// URL requested to test proxy connection
+const TEST_URL: &str = "https://media-cdn-zspms.kurogame.net/pnswebsite/website2.0/json/G167/MainMenu.json";
+
+const PROXY_VARIABLES: &[&str] = &[
+    "http_proxy", "HTTP_PROXY",
+    "https_proxy", "HTTPS_PROXY",
+    "all_proxy", "ALL_PROXY"
+];
+
+/// `SSL_CERT_FILE` is read by `rustls-native-certs` which is used by `minreq`,
+/// `GIT_SSL_CAINFO` by git and `CURL_CA_BUNDLE` by curl
+const CA_BUNDLE_VARIABLES: &[&str] = &[
+    "SSL_CERT_FILE",
+    "GIT_SSL_CAINFO",
+    "CURL_CA_BUNDLE"
+];
+
+/// Proxy settings read on the launcher start
+static APPLIED_PROXY: OnceLock<Proxy> = OnceLock::new();
+
+/// Bridge used by the launcher network clients
+static BRIDGE: OnceLock<RunningBridge> = OnceLock::new();
+
+/// Values of the environment variables before they were changed by `apply_from_settings`
+static ORIGINAL_VARIABLES: OnceLock<Vec<(&'static str, Option<OsString>)>> = OnceLock::new();
+
+/// Apply proxy from the launcher settings
+///
+/// The SDK's downloaders and installers, `minreq` requests and git components sync
+/// read proxy and certificates only from the standard environment variables.
+/// So the variables are set for the whole launcher process and point to the local bridge,
+/// which forwards connections to the proxy. Real proxy credentials are not stored in them,
+/// and the game is run without these variables (see `restore_variables`).
+///
+/// Environment variables can't be changed while other threads may read them,
+/// so this must be called on the launcher start before GTK or any other threads are started.
+/// Changed proxy settings are applied after the launcher restart.
+///
+/// Logger is not initialized yet at this moment, so applied proxy is returned to be logged later
+pub fn apply_from_settings() -> anyhow::Result<Option<Proxy>> {
+    let proxy = Settings::get()?.network.proxy;
+
+    let _ = APPLIED_PROXY.set(proxy.clone());
+
+    if !proxy.is_set() {
+        return Ok(None);
+    }
+
+    let bridge = Bridge::bind(Some(proxy.clone()))?;
+    let url = bridge.url()?;
+
+    let mut changed = PROXY_VARIABLES.to_vec();
+
+    if proxy.ca_bundle.is_some() {
+        changed.extend_from_slice(CA_BUNDLE_VARIABLES);
+    }
+
+    let _ = ORIGINAL_VARIABLES.set(changed.iter().map(|name| (*name, std::env::var_os(name))).collect());
+
+    for name in PROXY_VARIABLES {
+        std::env::set_var(name, &url);
+    }
+
+    if let Some(path) = &proxy.ca_bundle {
+        for name in CA_BUNDLE_VARIABLES {
+            std::env::set_var(name, path);
+        }
+    }
+
+    let _ = BRIDGE.set(bridge.spawn()?);
+
+    Ok(Some(proxy))
+}
+
+/// Check if the proxy is applied to the launcher process
+#[inline]
+pub fn is_applied() -> bool {
+    BRIDGE.get().is_some()
+}
+
+/// Check if given proxy settings differ from the applied ones
+pub fn restart_required(proxy: &Proxy) -> bool {
+    let applied = APPLIED_PROXY.get().cloned().unwrap_or_default();
+
+    (applied.is_set() || proxy.is_set()) && &applied != proxy
+}
+
+/// Restore environment variables changed by `apply_from_settings` for the child process
+pub fn restore_variables(command: &mut Command) {
+    for (name, value) in ORIGINAL_VARIABLES.get().into_iter().flatten() {
+        match value {
+            Some(value) => command.env(name, value),
+            None => command.env_remove(name)
+        };
+    }
+}
+
+/// Check that the game's servers can be reached with given proxy settings
+///
+/// Request is sent through a temporary bridge with these settings, so they
+/// can be tested before the launcher restart. Custom CA bundle is loaded only
+/// on the launcher start, so the applied one is used.
+///
+/// Returns response time
+pub fn test_connection(proxy: &Proxy) -> anyhow::Result<Duration> {
+    let bridge = Bridge::bind(proxy.is_set().then(|| proxy.clone()))?.spawn()?;
+
+    let started = Instant::now();
+
+    let response = minreq::head(TEST_URL)
+        .with_proxy(minreq::Proxy::new(bridge.url())?)
+        .with_timeout(TEST_TIMEOUT)
+        .send()?;
+
+    if response.status_code >= 400 {
+        anyhow::bail!("{} {}", response.status_code, response.reason_phrase);
+    }
+
+    Ok(started.elapsed())
+}
@@ -0,0 +1,119 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Time window used to calculate current download speed
+const SPEED_WINDOW: Duration = Duration::from_secs(5);
+
+/// Download speed meter
+#[derive(Debug, Clone)]
+pub struct SpeedMeter {
+    started: Instant,
+    start_bytes: Option<u64>,
+
+    /// (time, downloaded bytes) samples within the `SPEED_WINDOW`
+    samples: VecDeque<(Instant, u64)>
+}
+
+impl Default for SpeedMeter {
+    fn default() -> Self {
+        Self {
+            started: Instant::now(),
+            start_bytes: None,
+            samples: VecDeque::new()
+        }
+    }
+}
+
+impl SpeedMeter {
+    /// Add new downloaded bytes sample. New measurement is started if `curr` went back
+    pub fn update(&mut self, curr: u64) {
+        let now = Instant::now();
+
+        if let Some((_, last)) = self.samples.back() {
+            if curr < *last {
+                *self = Self::default();
+            }
+        }
+
+        if self.start_bytes.is_none() {
+            self.start_bytes = Some(curr);
+        }
+
+        self.samples.push_back((now, curr));
+
+        while let Some((time, _)) = self.samples.front() {
+            if now.duration_since(*time) > SPEED_WINDOW {
+                self.samples.pop_front();
+            } else {
+                break;
+            }
+        }
+    }
+
+    /// Current download speed in bytes per second
+    pub fn speed(&self) -> Option<u64> {
+        let (first_time, first_bytes) = self.samples.front()?;
+        let (last_time, last_bytes) = self.samples.back()?;
+
+        let elapsed = last_time.duration_since(*first_time).as_secs_f64();
+
+        if elapsed < 0.5 {
+            return None;
+        }
+
+        Some(((last_bytes - first_bytes) as f64 / elapsed) as u64)
+    }
+
+    /// Average download speed since measurement was started in bytes per second
+    pub fn average_speed(&self) -> Option<u64> {
+        let start_bytes = self.start_bytes?;
+        let (_, last_bytes) = self.samples.back()?;
+
+        let elapsed = self.started.elapsed().as_secs_f64();
+
+        if elapsed < 1.0 {
+            return None;
+        }
+
+        Some(((last_bytes - start_bytes) as f64 / elapsed) as u64)
+    }
+
+    /// Estimated time to download `total` bytes at current speed
+    pub fn eta(&self, total: u64) -> Option<Duration> {
+        let speed = self.speed().filter(|speed| *speed > 0)?;
+        let (_, curr) = self.samples.back()?;
+
+        Some(Duration::from_secs(total.saturating_sub(*curr) / speed))
+    }
+}
+
+/// Get average speed of the recent finished download in bytes per second
+pub fn recent_speed() -> Option<u64> {
+    std::fs::read_to_string(crate::RECENT_SPEED_FILE.as_path())
+        .ok()?
+        .trim()
+        .parse()
+        .ok()
+}
+
+/// Remember average speed of the finished download
+pub fn save_recent_speed(speed: u64) {
+    if let Err(err) = std::fs::write(crate::RECENT_SPEED_FILE.as_path(), speed.to_string()) {
+        tracing::warn!("Failed to save recent download speed: {err}");
+    }
+}
+
+/// Format duration as `1h 2m` or `3m 4s`
+pub fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+
+    let (hours, minutes, seconds) = (secs / 3600, secs % 3600 / 60, secs % 60);
+
+    if hours > 0 {
+        format!("{hours}h {minutes}m")
+    } else if minutes > 0 {
+        format!("{minutes}m {seconds}s")
+    } else {
+        format!("{seconds}s")
+    }
+}
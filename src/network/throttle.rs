@@ -0,0 +1,109 @@
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use anime_launcher_sdk::anime_game_core::prelude::*;
+
+use crate::settings::Settings;
+
+/// How often speed limit is re-read from the settings
+/// so scheduled limits are applied to running downloads
+const LIMIT_UPDATE_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Download speed limiter
+///
+/// SDK downloaders report their progress from the downloading thread,
+/// so we limit their speed by sleeping in the progress callback
+#[derive(Debug)]
+pub struct Throttle {
+    /// Limit in bytes per second
+    limit: Option<u64>,
+    limit_updated: Instant,
+
+    /// Time and downloaded bytes when current measuring window was started
+    window_start: Instant,
+    window_bytes: u64,
+
+    last_bytes: u64
+}
+
+impl Default for Throttle {
+    fn default() -> Self {
+        Self {
+            limit: Self::get_limit(),
+            limit_updated: Instant::now(),
+
+            window_start: Instant::now(),
+            window_bytes: 0,
+
+            last_bytes: 0
+        }
+    }
+}
+
+impl Throttle {
+    fn get_limit() -> Option<u64> {
+        Settings::get().ok()?.network.speed_limit.current()
+    }
+
+    fn reset(&mut self, curr: u64) {
+        self.window_start = Instant::now();
+        self.window_bytes = curr;
+    }
+
+    /// Block current thread if `curr` downloaded bytes were received faster than allowed
+    pub fn wait(&mut self, curr: u64) {
+        if self.limit_updated.elapsed() >= LIMIT_UPDATE_INTERVAL {
+            let limit = Self::get_limit();
+
+            if limit != self.limit {
+                tracing::debug!("Download speed limit changed: {limit:?}");
+
+                self.limit = limit;
+
+                self.reset(curr);
+            }
+
+            self.limit_updated = Instant::now();
+        }
+
+        // New download was started (or continued from some partial file)
+        if curr < self.last_bytes || self.last_bytes == 0 {
+            self.reset(curr);
+        }
+
+        self.last_bytes = curr;
+
+        if let Some(limit) = self.limit {
+            let expected = Duration::from_secs_f64(curr.saturating_sub(self.window_bytes) as f64 / limit as f64);
+            let elapsed = self.window_start.elapsed();
+
+            if expected > elapsed {
+                std::thread::sleep(expected - elapsed);
+            }
+        }
+    }
+}
+
+/// Limit download speed of the installer which reports its progress to the given updater
+pub fn throttle_installer(updater: impl Fn(InstallerUpdate) + Clone + Send + 'static) -> impl Fn(InstallerUpdate) + Clone + Send + 'static {
+    let throttle = Arc::new(Mutex::new(Throttle::default()));
+
+    move |update| {
+        if let InstallerUpdate::DownloadingProgress(curr, _) = &update {
+            throttle.lock().unwrap().wait(*curr);
+        }
+
+        updater(update);
+    }
+}
+
+/// Limit download speed of the downloader which reports its progress to the given callback
+pub fn throttle_downloader(callback: impl Fn(u64, u64) + Clone + Send + 'static) -> impl Fn(u64, u64) + Clone + Send + 'static {
+    let throttle = Arc::new(Mutex::new(Throttle::default()));
+
+    move |curr, total| {
+        throttle.lock().unwrap().wait(curr);
+
+        callback(curr, total);
+    }
+}
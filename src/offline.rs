@@ -0,0 +1,117 @@
+use std::net::{TcpStream, ToSocketAddrs};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use anime_launcher_sdk::wincompatlib::prelude::*;
+use anime_launcher_sdk::anime_game_core::patches::mfc140;
+use anime_launcher_sdk::pgr::states::LauncherState;
+
+use crate::*;
+use crate::settings::Settings;
+
+/// Host used to check if the game's servers can be reached
+const CHECK_HOST: (&str, u16) = ("media-cdn-zspms.kurogame.net", 443);
+
+const CHECK_TIMEOUT: Duration = Duration::from_secs(3);
+
+static OFFLINE: AtomicBool = AtomicBool::new(false);
+
+/// Check if the launcher works in offline mode
+///
+/// In offline mode all startup network tasks are skipped,
+/// and launcher state is evaluated using only local files
+#[inline]
+pub fn is_offline() -> bool {
+    OFFLINE.load(Ordering::Relaxed)
+}
+
+#[inline]
+pub fn set_offline(offline: bool) {
+    OFFLINE.store(offline, Ordering::Relaxed);
+}
+
+/// Check if the network is unavailable
+///
+/// If proxy is enabled then only the proxy server is checked.
+/// DNS resolution can't be timed out, so this is run as a startup task on the launcher start
+pub fn detect() -> bool {
+    let proxy = Settings::get()
+        .map(|settings| settings.network.proxy)
+        .unwrap_or_default();
+
+    let addrs = if proxy.is_set() {
+        (proxy.host.as_str(), proxy.port).to_socket_addrs()
+    } else {
+        CHECK_HOST.to_socket_addrs()
+    };
+
+    // DNS resolution failure usually means that there's no network at all
+    let Ok(addrs) = addrs else {
+        return true;
+    };
+
+    for addr in addrs {
+        if TcpStream::connect_timeout(&addr, CHECK_TIMEOUT).is_ok() {
+            return false;
+        }
+    }
+
+    true
+}
+
+/// Get launcher state without network requests
+///
+/// Game version and updates can't be checked without the network,
+/// so `LauncherState::Launch` is returned if the game, wine, prefix, mfc140 and fonts are installed locally
+#[inline]
+pub fn local_state() -> anyhow::Result<LauncherState> {
+    local_state_with_diff(None)
+}
+
+/// Get launcher state without network requests using already found game version diff
+///
+/// If `diff` is `None` then it works like `local_state`
+pub fn local_state_with_diff(diff: Option<VersionDiff>) -> anyhow::Result<LauncherState> {
+    let config = Config::get()?;
+
+    if diff.is_none() && !GAME.is_installed() {
+        anyhow::bail!("Game is not installed");
+    }
+
+    match config.get_selected_wine()? {
+        Some(wine) if config.game.wine.builds.join(&wine.name).exists() => (),
+
+        _ => return Ok(LauncherState::WineNotInstalled)
+    }
+
+    let prefix = config.get_wine_prefix_path();
+
+    if !prefix.join("drive_c").exists() {
+        return Ok(LauncherState::PrefixNotExists);
+    }
+
+    if !mfc140::is_installed(&prefix) {
+        return Ok(LauncherState::Mfc140NotInstalled);
+    }
+
+    let fonts = Font::iterator()
+        .filter(|font| !font.is_installed(&prefix))
+        .collect::<Vec<_>>();
+
+    if !fonts.is_empty() {
+        return Ok(LauncherState::FontsNotInstalled(fonts));
+    }
+
+    match diff {
+        Some(VersionDiff::Latest(_)) => Ok(LauncherState::Launch),
+
+        Some(diff @ VersionDiff::Outdated { .. }) => Ok(LauncherState::GameUpdateAvailable(diff)),
+        Some(diff @ VersionDiff::NotInstalled { .. }) => Ok(LauncherState::GameNotInstalled(diff)),
+
+        None => {
+            tracing::info!("Installed game version: {}", GAME.get_version()?);
+
+            Ok(LauncherState::Launch)
+        }
+    }
+}
@@ -0,0 +1,263 @@
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use serde::{Serialize, Deserialize};
+
+/// Launcher-specific settings which are not a part of the SDK's config schema
+///
+/// Stored in the `settings.json` file in the launcher folder
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub network: NetworkSettings,
+    pub auto_update: AutoUpdate,
+    pub repair: RepairSettings
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NetworkSettings {
+    pub speed_limit: SpeedLimit,
+
+    /// Base URLs of the game archives mirrors used when the official CDN fails
+    pub mirrors: Vec<String>,
+
+    /// Download attempts for each source before switching to the next one
+    pub retries: u32,
+
+    pub proxy: Proxy
+}
+
+impl Default for NetworkSettings {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            speed_limit: SpeedLimit::default(),
+            mirrors: Vec::new(),
+            retries: 3,
+            proxy: Proxy::default()
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SpeedLimit {
+    pub enabled: bool,
+
+    /// Download speed limit in KiB/s
+    pub limit: u64,
+
+    pub schedule: SpeedLimitSchedule
+}
+
+impl Default for SpeedLimit {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            limit: 2048,
+            schedule: SpeedLimitSchedule::default()
+        }
+    }
+}
+
+/// Time range when downloads are not limited
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SpeedLimitSchedule {
+    pub enabled: bool,
+
+    /// Hour (0-23, local time) when unlimited period starts
+    pub unlimited_from: u8,
+
+    /// Hour (0-23, local time) when unlimited period ends
+    pub unlimited_to: u8
+}
+
+impl Default for SpeedLimitSchedule {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            unlimited_from: 0,
+            unlimited_to: 8
+        }
+    }
+}
+
+impl SpeedLimitSchedule {
+    /// Check if given hour is within unlimited period.
+    /// Period can go over midnight (e.g. from 22 to 6)
+    pub fn is_unlimited(&self, hour: u8) -> bool {
+        if self.unlimited_from <= self.unlimited_to {
+            (self.unlimited_from..self.unlimited_to).contains(&hour)
+        } else {
+            hour >= self.unlimited_from || hour < self.unlimited_to
+        }
+    }
+}
+
+impl SpeedLimit {
+    /// Get current speed limit in bytes per second, if there's any
+    pub fn current(&self) -> Option<u64> {
+        if !self.enabled || self.limit == 0 {
+            return None;
+        }
+
+        if self.schedule.enabled {
+            let hour = gtk::glib::DateTime::now_local()
+                .map(|now| now.hour() as u8);
+
+            if let Ok(hour) = hour {
+                if self.schedule.is_unlimited(hour) {
+                    return None;
+                }
+            }
+        }
+
+        Some(self.limit * 1024)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProxyKind {
+    #[default]
+    Http,
+    Socks5
+}
+
+/// Proxy used for the launcher network traffic
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Proxy {
+    pub enabled: bool,
+    pub kind: ProxyKind,
+
+    pub host: String,
+    pub port: u16,
+
+    /// Empty if proxy doesn't require authorization
+    pub username: String,
+    pub password: String,
+
+    /// Custom certificate authorities bundle (PEM file)
+    pub ca_bundle: Option<PathBuf>
+}
+
+impl Default for Proxy {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            kind: ProxyKind::default(),
+
+            host: String::new(),
+            port: 8080,
+
+            username: String::new(),
+            password: String::new(),
+
+            ca_bundle: None
+        }
+    }
+}
+
+impl Proxy {
+    /// Check if proxy is enabled and its server is set
+    #[inline]
+    pub fn is_set(&self) -> bool {
+        self.enabled && !self.host.is_empty()
+    }
+}
+
+/// Background game updates
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AutoUpdate {
+    pub enabled: bool,
+
+    /// Check for updates every N hours
+    pub interval: u64,
+
+    /// Check for updates once a day at this hour (0-23, local time) instead of using interval
+    pub at_hour: Option<u8>
+}
+
+impl Default for AutoUpdate {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval: 6,
+            at_hour: None
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RepairSettings {
+    /// Re-hash all the game files instead of skipping ones which weren't changed since the last verification
+    pub force_full_verify: bool,
+
+    /// Glob patterns of game files which are never reported as broken and never repaired
+    /// (e.g. custom configs or ReShade files)
+    pub exclusions: Vec<String>,
+
+    /// Verify files changed by the installed game update and repair broken ones
+    pub verify_after_update: bool
+}
+
+impl Default for RepairSettings {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            force_full_verify: false,
+            exclusions: Vec::new(),
+            verify_after_update: true
+        }
+    }
+}
+
+lazy_static::lazy_static! {
+    static ref CACHED: Mutex<Option<Settings>> = Mutex::new(None);
+}
+
+impl Settings {
+    /// Get launcher settings. Default settings are returned if the file doesn't exist
+    pub fn get() -> anyhow::Result<Self> {
+        let mut cached = CACHED.lock().unwrap();
+
+        if let Some(settings) = cached.as_ref() {
+            return Ok(settings.clone());
+        }
+
+        let settings = if crate::SETTINGS_FILE.exists() {
+            serde_json::from_slice(&std::fs::read(crate::SETTINGS_FILE.as_path())?)?
+        } else {
+            Self::default()
+        };
+
+        *cached = Some(settings.clone());
+
+        Ok(settings)
+    }
+
+    /// Save launcher settings
+    pub fn update_raw(settings: Self) -> anyhow::Result<()> {
+        std::fs::write(crate::SETTINGS_FILE.as_path(), serde_json::to_string_pretty(&settings)?)?;
+
+        *CACHED.lock().unwrap() = Some(settings);
+
+        Ok(())
+    }
+
+    /// Save launcher settings, logging an error if it failed
+    pub fn update(settings: Self) {
+        if let Err(err) = Self::update_raw(settings) {
+            tracing::error!("Failed to update launcher settings: {err}");
+        }
+    }
+}
@@ -6,6 +6,9 @@ use adw::prelude::*;
 use anime_launcher_sdk::anime_game_core::prelude::*;
 
 use crate::i18n::*;
+use crate::actions::ActionUpdate;
+use crate::network::SpeedMeter;
+use crate::network::speed::format_duration;
 
 pub struct ProgressBarInit {
     pub caption: Option<String>,
@@ -23,9 +26,21 @@ pub struct ProgressBar {
     pub fraction: f64,
     pub caption: Option<String>,
 
+    /// Current step of the chained action (e.g. "Step 2: Create prefix")
+    pub step: Option<String>,
+
     /// e.g. (53.21 MB, 10 GB)
     pub downloaded: Option<(String, String)>,
 
+    /// Current progress is measured in bytes so its speed can be shown
+    pub in_bytes: bool,
+
+    /// Rolling average speed of the current stage
+    pub speed: SpeedMeter,
+
+    /// e.g. (5.2 MB, 3m 12s)
+    pub speed_eta: Option<(String, Option<String>)>,
+
     /// Add progress percentage (`XX.YY%`) suffix
     pub display_progress: bool,
 
@@ -46,6 +61,7 @@ pub enum ProgressBarMsg {
     UpdateProgress(u64, u64),
 
     UpdateFromState(InstallerUpdate),
+    UpdateFromAction(ActionUpdate),
 
     SetVisible(bool)
 }
@@ -73,6 +89,10 @@ impl SimpleAsyncComponent for ProgressBar {
             #[watch]
             set_text: Some(&match model.caption.clone() {
                 Some(mut caption) => {
+                    if let Some(step) = &model.step {
+                        caption = format!("{step}. {caption}");
+                    }
+
                     if model.display_progress {
                         caption = format!("{caption}: {:.2}%", model.fraction * 100.0);
                     }
@@ -83,6 +103,13 @@ impl SimpleAsyncComponent for ProgressBar {
                         }
                     }
 
+                    match &model.speed_eta {
+                        Some((speed, Some(eta))) => caption = format!("{caption} - {speed}/s, {}", tr_args("time-left", [("time", eta.clone().into())])),
+                        Some((speed, None)) => caption = format!("{caption} - {speed}/s"),
+
+                        None => ()
+                    }
+
                     caption
                 },
                 None => String::new()
@@ -98,7 +125,11 @@ impl SimpleAsyncComponent for ProgressBar {
         let model = ProgressBar {
             fraction: 0.0,
             caption: init.caption,
+            step: None,
             downloaded: None,
+            in_bytes: init.display_fraction,
+            speed: SpeedMeter::default(),
+            speed_eta: None,
             display_progress: init.display_progress,
             display_fraction: init.display_fraction,
             visible: init.visible
@@ -115,42 +146,55 @@ impl SimpleAsyncComponent for ProgressBar {
                 self.fraction = 0.0;
                 self.downloaded = None;
                 self.caption = None;
+                self.step = None;
+
+                self.reset_speed();
+            }
+
+            ProgressBarMsg::UpdateCaption(caption) => {
+                self.caption = caption;
+
+                self.reset_speed();
             }
 
-            ProgressBarMsg::UpdateCaption(caption) => self.caption = caption,
             ProgressBarMsg::DisplayProgress(value) => self.display_progress = value,
             ProgressBarMsg::DisplayFraction(value) => self.display_fraction = value,
 
             ProgressBarMsg::UpdateProgress(curr, total) => {
-                self.fraction = curr as f64 / total as f64;
+                self.in_bytes = self.display_fraction;
 
-                self.downloaded = Some((
-                    prettify_bytes(curr),
-                    prettify_bytes(total)
-                ));
+                self.update_progress(curr, total);
             }
 
-            ProgressBarMsg::UpdateFromState(state) => {
-                match state {
-                    InstallerUpdate::CheckingFreeSpace(_)  => self.caption = Some(tr("checking-free-space")),
-                    InstallerUpdate::DownloadingStarted(_) => self.caption = Some(tr("downloading")),
-                    InstallerUpdate::UnpackingStarted(_)   => self.caption = Some(tr("unpacking")),
+            ProgressBarMsg::UpdateFromState(state) => self.update_from_state(state),
+
+            ProgressBarMsg::UpdateFromAction(update) => {
+                match update {
+                    ActionUpdate::Installer(state) => {
+                        // Installers always report progress in bytes
+                        self.display_fraction = true;
+
+                        self.update_from_state(state);
+                    }
+
+                    ActionUpdate::Stage(stage) => {
+                        self.fraction = 0.0;
+                        self.downloaded = None;
 
-                    InstallerUpdate::DownloadingProgress(curr, total) |
-                    InstallerUpdate::UnpackingProgress(curr, total) => {
-                        self.fraction = curr as f64 / total as f64;
+                        self.reset_speed();
 
-                        self.downloaded = Some((
-                            prettify_bytes(curr),
-                            prettify_bytes(total)
-                        ));
+                        self.caption = Some(stage.caption());
+                        self.display_fraction = stage.in_bytes();
+                        self.in_bytes = stage.in_bytes();
                     }
 
-                    InstallerUpdate::DownloadingFinished => tracing::info!("Downloading finished"),
-                    InstallerUpdate::UnpackingFinished   => tracing::info!("Unpacking finished"),
+                    ActionUpdate::Step(step) => self.step = step,
+
+                    ActionUpdate::Progress(curr, total) => self.update_progress(curr, total),
+
+                    ActionUpdate::Error(err) => tracing::error!("Action error: {err}"),
 
-                    InstallerUpdate::DownloadingError(err) => tracing::error!("Downloading error: {:?}", err),
-                    InstallerUpdate::UnpackingError(err) => tracing::error!("Unpacking error: {:?}", err)
+                    ActionUpdate::Notice { title, .. } => tracing::info!("Action notice: {title}")
                 }
             }
 
@@ -158,3 +202,57 @@ impl SimpleAsyncComponent for ProgressBar {
         }
     }
 }
+
+impl ProgressBar {
+    fn reset_speed(&mut self) {
+        self.speed = SpeedMeter::default();
+        self.speed_eta = None;
+    }
+
+    fn update_progress(&mut self, curr: u64, total: u64) {
+        self.fraction = curr as f64 / total as f64;
+
+        self.downloaded = Some((
+            prettify_bytes(curr),
+            prettify_bytes(total)
+        ));
+
+        if self.in_bytes {
+            self.speed.update(curr);
+
+            self.speed_eta = self.speed.speed().map(|speed| {
+                (prettify_bytes(speed), self.speed.eta(total).map(format_duration))
+            });
+        }
+    }
+
+    fn update_from_state(&mut self, state: InstallerUpdate) {
+        // Installers always report progress in bytes
+        self.in_bytes = true;
+
+        match state {
+            InstallerUpdate::CheckingFreeSpace(_)  => self.caption = Some(tr("checking-free-space")),
+
+            InstallerUpdate::DownloadingStarted(_) => {
+                self.caption = Some(tr("downloading"));
+
+                self.reset_speed();
+            }
+
+            InstallerUpdate::UnpackingStarted(_) => {
+                self.caption = Some(tr("unpacking"));
+
+                self.reset_speed();
+            }
+
+            InstallerUpdate::DownloadingProgress(curr, total) |
+            InstallerUpdate::UnpackingProgress(curr, total) => self.update_progress(curr, total),
+
+            InstallerUpdate::DownloadingFinished => tracing::info!("Downloading finished"),
+            InstallerUpdate::UnpackingFinished   => tracing::info!("Unpacking finished"),
+
+            InstallerUpdate::DownloadingError(err) => tracing::error!("Downloading error: {:?}", err),
+            InstallerUpdate::UnpackingError(err) => tracing::error!("Unpacking error: {:?}", err)
+        }
+    }
+}
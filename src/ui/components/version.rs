@@ -166,7 +166,7 @@ impl SimpleAsyncComponent for ComponentVersion {
                                 progress_bar_sender.send(ProgressBarMsg::Reset);
                                 progress_bar_sender.send(ProgressBarMsg::SetVisible(true));
 
-                                installer.install(download_folder, move |state| {
+                                installer.install(download_folder, crate::network::throttle_installer(move |state| {
                                     match &state {
                                         InstallerUpdate::UnpackingFinished |
                                         InstallerUpdate::DownloadingError(_) |
@@ -187,7 +187,7 @@ impl SimpleAsyncComponent for ComponentVersion {
                                     }
 
                                     progress_bar_sender.send(ProgressBarMsg::UpdateFromState(state));
-                                });
+                                }));
                             }));
                         }
                     }
@@ -343,7 +343,10 @@ impl SimpleAsyncComponent for DefaultPathsApp {
 
                             // Restart the app
 
-                            std::process::Command::new(std::env::current_exe().unwrap()).spawn().unwrap();
+                            std::process::Command::new(std::env::current_exe().unwrap())
+                                .arg(crate::instance::RESTART_ARG)
+                                .spawn()
+                                .unwrap();
 
                             relm4::main_application().quit();
                         }
@@ -378,7 +378,7 @@ impl SimpleAsyncComponent for DownloadComponentsApp {
                                         .expect("Failed to create wine builds directory");
                                 }
 
-                                installer.install(&config.game.wine.builds, move |update| {
+                                installer.install(&config.game.wine.builds, crate::network::throttle_installer(move |update| {
                                     match &update {
                                         InstallerUpdate::DownloadingError(err) => {
                                             tracing::error!("Failed to download wine: {err}");
@@ -420,7 +420,7 @@ impl SimpleAsyncComponent for DownloadComponentsApp {
                                     }
 
                                     progress_bar_input.send(ProgressBarMsg::UpdateFromState(update));
-                                });
+                                }));
                             }
 
                             Err(err) => {
@@ -504,7 +504,7 @@ impl SimpleAsyncComponent for DownloadComponentsApp {
                                         .expect("Failed to create DXVK builds directory");
                                 }
 
-                                installer.install(&config.game.dxvk.builds, move |update| {
+                                installer.install(&config.game.dxvk.builds, crate::network::throttle_installer(move |update| {
                                     match &update {
                                         InstallerUpdate::DownloadingError(err) => {
                                             tracing::error!("Failed to download dxvk: {err}");
@@ -533,7 +533,7 @@ impl SimpleAsyncComponent for DownloadComponentsApp {
                                     }
 
                                     progress_bar_input.send(ProgressBarMsg::UpdateFromState(update));
-                                });
+                                }));
                             }
 
                             Err(err) => {
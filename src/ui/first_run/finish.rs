@@ -84,7 +84,10 @@ impl SimpleAsyncComponent for FinishApp {
     async fn update(&mut self, msg: Self::Input, _sender: AsyncComponentSender<Self>) {
         match msg {
             FinishAppMsg::Restart => {
-                std::process::Command::new(std::env::current_exe().unwrap()).spawn().unwrap();
+                std::process::Command::new(std::env::current_exe().unwrap())
+                    .arg(crate::instance::RESTART_ARG)
+                    .spawn()
+                    .unwrap();
 
                 relm4::main_application().quit();
             }
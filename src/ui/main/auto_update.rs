@@ -0,0 +1,165 @@
+use std::io::Write;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use relm4::{
+    prelude::*,
+    Sender
+};
+
+use gtk::prelude::*;
+use gtk::{gio, glib};
+use gtk::glib::clone;
+
+use crate::*;
+use crate::i18n::*;
+use crate::ui::components::*;
+use crate::settings::{Settings, AutoUpdate};
+use crate::actions::{DownloadQueue, DownloadStatus};
+
+use super::{App, AppMsg};
+
+/// How often the launcher checks if it's time to look for game updates
+pub const CHECK_INTERVAL_SECS: u32 = 60;
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|time| time.as_secs())
+        .unwrap_or_default()
+}
+
+fn last_check() -> u64 {
+    std::fs::read_to_string(LAST_UPDATE_CHECK_FILE.as_path())
+        .ok()
+        .and_then(|time| time.trim().parse().ok())
+        .unwrap_or_default()
+}
+
+/// Check if it's time to look for game updates
+pub fn is_due(settings: &AutoUpdate) -> bool {
+    if !settings.enabled || crate::offline::is_offline() {
+        return false;
+    }
+
+    let elapsed = now().saturating_sub(last_check());
+
+    match settings.at_hour {
+        Some(hour) => {
+            let Ok(local) = glib::DateTime::now_local() else {
+                return false;
+            };
+
+            // Don't check twice in the same hour
+            local.hour() as u8 == hour && elapsed >= 60 * 60
+        }
+
+        None => elapsed >= settings.interval.max(1) * 60 * 60
+    }
+}
+
+/// Look for game updates in background thread and send `AppMsg::AutoUpdate` if there's one
+pub fn check(sender: ComponentSender<App>) {
+    #[allow(unused_must_use)] {
+        std::fs::write(LAST_UPDATE_CHECK_FILE.as_path(), now().to_string());
+    }
+
+    std::thread::spawn(move || {
+        tracing::info!("Checking for game updates");
+
+        match GAME.try_get_diff() {
+            Ok(diff @ VersionDiff::Outdated { .. }) => sender.input(AppMsg::AutoUpdate(diff)),
+
+            Ok(_) => tracing::info!("Game is already updated"),
+
+            Err(err) => tracing::error!("Failed to check for game updates: {err}")
+        }
+    });
+}
+
+/// Download game update without user interaction, then leave a notification and `updates.log` entry
+pub fn auto_update(sender: ComponentSender<App>, progress_bar_input: Sender<ProgressBarMsg>, downloads: &DownloadQueue, diff: VersionDiff) {
+    let VersionDiff::Outdated { current, latest, .. } = &diff else {
+        return;
+    };
+
+    let (current, latest) = (current.to_string(), latest.to_string());
+
+    tracing::info!("Automatically updating the game from {current} to {latest}");
+
+    sender.input(AppMsg::SetDownloading(true));
+
+    let updater = super::action_updater(sender.clone(), progress_bar_input);
+
+    downloads.push(move |handle| actions::download_diff(diff.clone(), handle, updater.clone()), clone!(@strong sender => move |result| {
+        match result {
+            Ok(DownloadStatus::Finished) => {
+                tracing::info!("Game was automatically updated from {current} to {latest}");
+
+                log_update(&current, &latest);
+
+                notify(tr("game-updated"), tr_args("game-updated-description", [
+                    ("old", current.into()),
+                    ("new", latest.into())
+                ]));
+            }
+
+            Ok(status) => tracing::info!("Automatic game update was interrupted: {status:?}"),
+
+            Err(err) => {
+                tracing::error!("Failed to automatically update the game: {err}");
+
+                notify(err.title.clone(), err.description.clone().unwrap_or_default());
+
+                sender.input(AppMsg::Toast {
+                    title: err.title,
+                    description: err.description
+                });
+            }
+        }
+
+        sender.input(AppMsg::SetDownloading(false));
+        sender.input(AppMsg::UpdateLauncherState {
+            perform_on_download_needed: false,
+            show_status_page: false
+        });
+    }));
+}
+
+fn log_update(current: &str, latest: &str) {
+    let time = glib::DateTime::now_local()
+        .and_then(|time| time.format("%F %T"))
+        .map(|time| time.to_string())
+        .unwrap_or_default();
+
+    let file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(UPDATES_LOG_FILE.as_path());
+
+    let result = file.and_then(|mut file| writeln!(file, "[{time}] Updated the game from {current} to {latest}"));
+
+    if let Err(err) = result {
+        tracing::error!("Failed to write updates log: {err}");
+    }
+}
+
+fn notify(title: String, body: String) {
+    let notification = gio::Notification::new(&title);
+
+    notification.set_body(Some(&body));
+
+    relm4::main_application().send_notification(Some("auto-update"), &notification);
+}
+
+/// Start periodic updates checking
+pub fn start(sender: ComponentSender<App>) {
+    glib::timeout_add_seconds_local(CHECK_INTERVAL_SECS, move || {
+        if let Ok(settings) = Settings::get() {
+            if is_due(&settings.auto_update) {
+                sender.input(AppMsg::CheckAutoUpdate);
+            }
+        }
+
+        glib::Continue(true)
+    });
+}
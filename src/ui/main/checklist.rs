@@ -0,0 +1,129 @@
+use relm4::prelude::*;
+
+use gtk::prelude::*;
+use adw::prelude::*;
+
+use gtk::glib;
+use gtk::glib::clone;
+
+use crate::i18n::*;
+use crate::checklist::*;
+
+use super::{App, AppMsg, MAIN_WINDOW};
+
+fn check_row(sender: &ComponentSender<App>, window: &adw::Window, check: Check) -> adw::ActionRow {
+    let row = adw::ActionRow::new();
+
+    row.set_title(&check.name);
+    row.set_subtitle(&check.reason);
+
+    let (icon, class) = match check.status {
+        CheckStatus::Passed  => ("emblem-ok-symbolic", "success"),
+        CheckStatus::Failed  => ("dialog-warning-symbolic", "error"),
+        CheckStatus::Pending => ("content-loading-symbolic", "dim-label")
+    };
+
+    let status = gtk::Image::from_icon_name(icon);
+
+    status.add_css_class(class);
+
+    row.add_prefix(&status);
+
+    if let Some(fix) = check.fix {
+        let button = gtk::Button::with_label(&fix.label());
+
+        button.set_valign(gtk::Align::Center);
+
+        button.connect_clicked(clone!(@strong sender, @weak window => move |_| {
+            sender.input(AppMsg::RunCheckFix(fix.clone()));
+
+            window.close();
+        }));
+
+        row.add_suffix(&button);
+    }
+
+    row
+}
+
+/// Show every launcher setup check with its status and fix action
+pub fn show(sender: ComponentSender<App>) {
+    let window = adw::Window::new();
+
+    window.set_title(Some(&tr("setup-checklist")));
+    window.set_default_size(520, 600);
+    window.set_modal(true);
+    window.set_transient_for(unsafe { MAIN_WINDOW.as_ref() });
+
+    let page = adw::PreferencesPage::new();
+
+    let content = gtk::Box::new(gtk::Orientation::Vertical, 0);
+
+    let header = adw::HeaderBar::new();
+    let fix_everything = gtk::Button::with_label(&tr("fix-everything"));
+
+    fix_everything.add_css_class("suggested-action");
+
+    fix_everything.connect_clicked(clone!(@strong sender, @weak window => move |_| {
+        sender.input(AppMsg::FixEverything);
+
+        window.close();
+    }));
+
+    header.pack_start(&fix_everything);
+
+    content.append(&header);
+    content.append(&page);
+
+    page.set_vexpand(true);
+
+    window.set_content(Some(&content));
+
+    // Checks are performed in background thread because game version check requires network
+    let (checks_sender, checks_receiver) = glib::MainContext::channel::<Vec<Check>>(glib::PRIORITY_DEFAULT);
+
+    let mut group: Option<adw::PreferencesGroup> = None;
+
+    checks_receiver.attach(None, clone!(@weak window, @weak page => @default-return glib::Continue(false), move |checks| {
+        if let Some(group) = group.take() {
+            page.remove(&group);
+        }
+
+        let new_group = adw::PreferencesGroup::new();
+
+        new_group.set_description(Some(&tr("setup-checklist-description")));
+
+        for check in checks {
+            new_group.add(&check_row(&sender, &window, check));
+        }
+
+        page.add(&new_group);
+
+        group = Some(new_group);
+
+        glib::Continue(true)
+    }));
+
+    std::thread::spawn(move || {
+        let mut checks = local_checks();
+
+        #[allow(unused_must_use)] {
+            let mut pending = checks.clone();
+
+            pending.push(Check {
+                name: tr("game"),
+                status: CheckStatus::Pending,
+                reason: tr("check-game-checking"),
+                fix: None
+            });
+
+            checks_sender.send(pending);
+
+            checks.push(game_check());
+
+            checks_sender.send(checks);
+        }
+    });
+
+    window.present();
+}
@@ -0,0 +1,305 @@
+use std::sync::Mutex;
+
+use relm4::prelude::*;
+
+use gtk::prelude::*;
+use gtk::{gio, glib};
+
+use anime_launcher_sdk::pgr::states::LauncherState;
+
+use super::{App, AppMsg};
+
+/// Object path used by the `GApplication` for the launcher's app id
+pub const OBJECT_PATH: &str = "/moe/launcher/an_anime_borb_launcher";
+
+pub const INTERFACE_NAME: &str = "moe.launcher.an_anime_borb_launcher.Control";
+
+const INTERFACE_XML: &str = r#"
+<node>
+    <interface name="moe.launcher.an_anime_borb_launcher.Control">
+        <method name="PerformAction" />
+        <method name="RepairGame" />
+        <method name="OpenPreferences" />
+        <method name="UpdateLauncherState" />
+
+        <method name="PauseDownload" />
+        <method name="ResumeDownload" />
+        <method name="CancelDownload" />
+
+        <!-- Launcher state name, e.g. "launch" or "game-update-available". Empty if unknown -->
+        <property name="State" type="s" access="read" />
+
+        <!-- true if launcher is downloading or repairing something -->
+        <property name="Downloading" type="b" access="read" />
+
+        <!-- (current, total) progress of the current download or repair stage -->
+        <property name="DownloadProgress" type="(tt)" access="read" />
+
+        <signal name="StateChanged">
+            <arg name="state" type="s" />
+        </signal>
+    </interface>
+</node>
+"#;
+
+#[derive(Debug, Default)]
+struct ControlState {
+    connection: Option<gio::DBusConnection>,
+
+    state: String,
+    downloading: bool,
+    progress: (u64, u64)
+}
+
+lazy_static::lazy_static! {
+    static ref STATE: Mutex<ControlState> = Mutex::new(ControlState::default());
+}
+
+/// Register control interface on the launcher's session bus connection
+///
+/// Method calls are forwarded to the main window as `AppMsg`
+pub fn register(sender: ComponentSender<App>) -> anyhow::Result<()> {
+    let Some(connection) = relm4::main_application().dbus_connection() else {
+        anyhow::bail!("Launcher is not registered on the session bus");
+    };
+
+    register_on(&connection, move |msg| sender.input(msg))?;
+
+    STATE.lock().unwrap().connection = Some(connection);
+
+    tracing::info!("Registered D-Bus control interface");
+
+    Ok(())
+}
+
+/// Get message which should be sent to the main window when the method is called
+fn method_msg(method: &str) -> Option<AppMsg> {
+    Some(match method {
+        "PerformAction"   => AppMsg::PerformAction,
+        "RepairGame"      => AppMsg::RepairGame,
+        "OpenPreferences" => AppMsg::OpenPreferences,
+
+        "PauseDownload"  => AppMsg::PauseDownload,
+        "ResumeDownload" => AppMsg::ResumeDownload,
+        "CancelDownload" => AppMsg::CancelDownload,
+
+        "UpdateLauncherState" => AppMsg::UpdateLauncherState {
+            perform_on_download_needed: false,
+            show_status_page: false
+        },
+
+        _ => return None
+    })
+}
+
+/// Register control interface on the given connection and pass messages of the called methods to `handler`
+fn register_on(connection: &gio::DBusConnection, handler: impl Fn(AppMsg) + 'static) -> anyhow::Result<()> {
+    let node = gio::DBusNodeInfo::for_xml(INTERFACE_XML)?;
+
+    let Some(interface) = node.lookup_interface(INTERFACE_NAME) else {
+        anyhow::bail!("Failed to find {INTERFACE_NAME} interface info");
+    };
+
+    connection.register_object(
+        OBJECT_PATH,
+        &interface,
+        move |_, _, _, _, method, _, invocation| {
+            tracing::debug!("Called D-Bus method: {method}");
+
+            match method_msg(method) {
+                Some(msg) => {
+                    handler(msg);
+
+                    invocation.return_value(None);
+                }
+
+                None => invocation.return_dbus_error("org.freedesktop.DBus.Error.UnknownMethod", &format!("Unknown method: {method}"))
+            }
+        },
+        |_, _, _, _, property| {
+            let state = STATE.lock().unwrap();
+
+            match property {
+                "State"            => state.state.to_variant(),
+                "Downloading"      => state.downloading.to_variant(),
+                "DownloadProgress" => state.progress.to_variant(),
+
+                _ => ().to_variant()
+            }
+        },
+        |_, _, _, _, _, _| false
+    )?;
+
+    Ok(())
+}
+
+/// Update `State` property and emit `StateChanged` signal
+pub fn set_state(state: Option<&LauncherState>) {
+    let name = state.map(crate::actions::state_name)
+        .unwrap_or_default()
+        .to_string();
+
+    let mut control = STATE.lock().unwrap();
+
+    if control.state != name {
+        control.state = name.clone();
+
+        control.emit("StateChanged", &(name.as_str(),).to_variant());
+        control.properties_changed("State", name.to_variant());
+    }
+}
+
+/// Update `Downloading` property
+pub fn set_downloading(downloading: bool) {
+    let mut control = STATE.lock().unwrap();
+
+    if control.downloading != downloading {
+        control.downloading = downloading;
+
+        if !downloading {
+            control.progress = (0, 0);
+        }
+
+        control.properties_changed("Downloading", downloading.to_variant());
+    }
+}
+
+/// Update `DownloadProgress` property
+///
+/// `PropertiesChanged` signal is emitted only when progress percent changes
+/// so we don't flood the bus with installer updates
+pub fn set_progress(curr: u64, total: u64) {
+    let mut control = STATE.lock().unwrap();
+
+    let percent = |(curr, total): (u64, u64)| {
+        if total > 0 {
+            curr * 100 / total
+        } else {
+            0
+        }
+    };
+
+    let changed = percent(control.progress) != percent((curr, total)) || control.progress.1 != total;
+
+    control.progress = (curr, total);
+
+    if changed {
+        control.properties_changed("DownloadProgress", (curr, total).to_variant());
+    }
+}
+
+impl ControlState {
+    fn emit(&self, signal: &str, params: &glib::Variant) {
+        if let Some(connection) = &self.connection {
+            if let Err(err) = connection.emit_signal(None, OBJECT_PATH, INTERFACE_NAME, signal, Some(params)) {
+                tracing::error!("Failed to emit D-Bus signal {signal}: {err}");
+            }
+        }
+    }
+
+    fn properties_changed(&self, property: &str, value: glib::Variant) {
+        if let Some(connection) = &self.connection {
+            let changed = glib::VariantDict::new(None);
+
+            changed.insert_value(property, &value);
+
+            let params = glib::Variant::tuple_from_iter([
+                INTERFACE_NAME.to_variant(),
+                changed.end(),
+                Vec::<String>::new().to_variant()
+            ]);
+
+            if let Err(err) = connection.emit_signal(None, OBJECT_PATH, "org.freedesktop.DBus.Properties", "PropertiesChanged", Some(&params)) {
+                tracing::error!("Failed to emit D-Bus properties change: {err}");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    use super::*;
+
+    fn connect(address: &str) -> gio::DBusConnection {
+        gio::DBusConnection::for_address_sync(
+            address,
+            gio::DBusConnectionFlags::AUTHENTICATION_CLIENT | gio::DBusConnectionFlags::MESSAGE_BUS_CONNECTION,
+            None,
+            None::<&gio::Cancellable>
+        ).expect("Failed to connect to the test bus")
+    }
+
+    fn call(connection: &gio::DBusConnection, name: &str, interface: &str, method: &str, params: Option<&glib::Variant>) -> Result<glib::Variant, glib::Error> {
+        connection.call_sync(
+            Some(name),
+            OBJECT_PATH,
+            interface,
+            method,
+            params,
+            None,
+            gio::DBusCallFlags::NONE,
+            5000,
+            None::<&gio::Cancellable>
+        )
+    }
+
+    /// Run control interface on a private dbus-daemon and call its methods from another connection
+    #[test]
+    fn methods_are_forwarded() {
+        let bus = gio::TestDBus::new(gio::TestDBusFlags::NONE);
+
+        bus.up();
+
+        let address = bus.bus_address().expect("Test bus is not started").to_string();
+        let context = glib::MainContext::new();
+
+        let received = context.with_thread_default(|| {
+            let connection = connect(&address);
+            let name = connection.unique_name().expect("Connection has no unique name").to_string();
+
+            let received = Arc::new(Mutex::new(Vec::new()));
+
+            register_on(&connection, {
+                let received = received.clone();
+
+                move |msg| received.lock().unwrap().push(format!("{msg:?}"))
+            }).expect("Failed to register control interface");
+
+            // Calls are blocking, so they're made from another thread
+            // while this one dispatches them
+            let client = std::thread::spawn(move || {
+                let connection = connect(&address);
+
+                call(&connection, &name, INTERFACE_NAME, "PerformAction", None).expect("PerformAction failed");
+                call(&connection, &name, INTERFACE_NAME, "RepairGame", None).expect("RepairGame failed");
+
+                let unknown = call(&connection, &name, INTERFACE_NAME, "RemoveGame", None);
+
+                let downloading = call(&connection, &name, "org.freedesktop.DBus.Properties", "Get", Some(&(INTERFACE_NAME, "Downloading").to_variant()))
+                    .expect("Failed to get Downloading property");
+
+                (unknown.is_err(), downloading)
+            });
+
+            while !client.is_finished() {
+                context.iteration(false);
+
+                std::thread::sleep(Duration::from_millis(10));
+            }
+
+            let (unknown_failed, downloading) = client.join().unwrap();
+
+            assert!(unknown_failed, "Unknown method must return an error");
+            assert_eq!(downloading.child_value(0).as_variant().and_then(|value| value.get::<bool>()), Some(false));
+
+            received
+        }).expect("Failed to acquire main context");
+
+        bus.down();
+
+        assert_eq!(*received.lock().unwrap(), vec![String::from("PerformAction"), String::from("RepairGame")]);
+    }
+}
@@ -6,58 +6,35 @@ use relm4::{
 use gtk::glib::clone;
 
 use crate::*;
-use crate::i18n::*;
 use crate::ui::components::*;
+use crate::actions::{DownloadQueue, DownloadStatus};
 
 use super::{App, AppMsg};
 
-pub fn download_diff(sender: ComponentSender<App>, progress_bar_input: Sender<ProgressBarMsg>, diff: VersionDiff) {
+pub fn download_diff(sender: ComponentSender<App>, progress_bar_input: Sender<ProgressBarMsg>, downloads: &DownloadQueue, diff: VersionDiff) {
     sender.input(AppMsg::SetDownloading(true));
 
-    std::thread::spawn(move || {
-        let config = Config::get().unwrap();
+    let updater = super::action_updater(sender.clone(), progress_bar_input);
 
-        let result = diff.install_to(config.game.path, clone!(@strong sender => move |state| {
-            match &state {
-                InstallerUpdate::DownloadingError(err) => {
-                    tracing::error!("Downloading failed: {err}");
-
-                    sender.input(AppMsg::Toast {
-                        title: tr("downloading-failed"),
-                        description: Some(err.to_string())
-                    });
-                }
-
-                InstallerUpdate::UnpackingError(err) => {
-                    tracing::error!("Unpacking failed: {err}");
-
-                    sender.input(AppMsg::Toast {
-                        title: tr("unpacking-failed"),
-                        description: Some(err.clone())
-                    });
-                }
+    downloads.push(move |handle| actions::download_diff(diff.clone(), handle, updater.clone()), clone!(@strong sender => move |result| {
+        let mut perform_on_download_needed = true;
 
-                _ => ()
-            }
+        match result {
+            Err(err) => {
+                sender.input(AppMsg::Toast {
+                    title: err.title,
+                    description: err.description
+                });
 
-            #[allow(unused_must_use)] {
-                progress_bar_input.send(ProgressBarMsg::UpdateFromState(state));
+                // Don't try to download something after state updating
+                // because we just failed to do it
+                perform_on_download_needed = false;
             }
-        }));
-
-        let mut perform_on_download_needed = true;
-
-        if let Err(err) = result {
-            tracing::error!("Downloading failed: {err}");
 
-            sender.input(AppMsg::Toast {
-                title: tr("downloading-failed"),
-                description: Some(err.to_string())
-            });
+            // Don't start the download again if user cancelled it
+            Ok(DownloadStatus::Cancelled) => perform_on_download_needed = false,
 
-            // Don't try to download something after state updating
-            // because we just failed to do it
-            perform_on_download_needed = false;
+            Ok(_) => ()
         }
 
         sender.input(AppMsg::SetDownloading(false));
@@ -65,5 +42,5 @@ pub fn download_diff(sender: ComponentSender<App>, progress_bar_input: Sender<Pr
             perform_on_download_needed,
             show_status_page: false
         });
-    });
+    }));
 }
@@ -0,0 +1,104 @@
+use relm4::prelude::*;
+
+use adw::prelude::*;
+
+use gtk::glib::clone;
+
+use crate::*;
+use crate::i18n::*;
+use crate::actions::DownloadPreview;
+use crate::network::speed::format_duration;
+
+use super::{App, AppMsg, MAIN_WINDOW};
+
+fn format_size(size: Option<u64>) -> String {
+    size.map(prettify_bytes).unwrap_or_else(|| String::from("?"))
+}
+
+fn describe(preview: &DownloadPreview) -> String {
+    let mut lines = vec![
+        format!("{}: {}", tr("download-size"), format_size(preview.download_size)),
+        format!("{}: {}", tr("unpacked-size"), format_size(preview.unpacked_size)),
+        String::new(),
+        format!("{} ({}): {}", tr("game-free-space"), preview.game_path.to_string_lossy(), format_size(preview.game_free_space)),
+        format!("{} ({}): {}", tr("temp-free-space"), preview.temp_path.to_string_lossy(), format_size(preview.temp_free_space)),
+        String::new(),
+        format!("{}: {}", tr("estimated-time"), preview.eta.map(format_duration).unwrap_or_else(|| String::from("?")))
+    ];
+
+    if !preview.enough_space() {
+        lines.push(String::new());
+        lines.push(tr("not-enough-space"));
+    }
+
+    lines.join("\n")
+}
+
+/// Show download size and disk space preview before downloading the diff
+pub fn show(sender: ComponentSender<App>, diff: VersionDiff) {
+    let preview = match actions::download_preview(&diff) {
+        Ok(preview) => preview,
+        Err(err) => {
+            sender.input(AppMsg::Toast {
+                title: err.title,
+                description: err.description
+            });
+
+            return;
+        }
+    };
+
+    let dialog = adw::MessageDialog::new(unsafe { MAIN_WINDOW.as_ref() }, Some(&tr("download-preview")), Some(&describe(&preview)));
+
+    dialog.add_response("cancel", &tr("cancel"));
+    dialog.add_response("change-temp", &tr("change-temp-folder"));
+    dialog.add_response("download", &tr("download"));
+
+    dialog.set_response_appearance("download", if preview.enough_space() {
+        adw::ResponseAppearance::Suggested
+    } else {
+        adw::ResponseAppearance::Destructive
+    });
+
+    dialog.set_default_response(Some("download"));
+    dialog.set_close_response("cancel");
+
+    dialog.connect_response(None, move |_, response| {
+        match response {
+            "download"    => sender.input(AppMsg::DownloadDiff(diff.clone())),
+            "change-temp" => sender.input(AppMsg::ChooseTempFolder(diff.clone())),
+
+            _ => ()
+        }
+    });
+
+    dialog.present();
+}
+
+/// Pick new temp folder and show download preview again
+pub fn choose_temp_folder(sender: ComponentSender<App>, diff: VersionDiff) {
+    gtk::glib::MainContext::default().spawn_local(clone!(@strong sender => async move {
+        let config = Config::get().unwrap_or_else(|_| CONFIG.clone());
+
+        let result = rfd::AsyncFileDialog::new()
+            .set_directory(config.launcher.temp.unwrap_or_else(std::env::temp_dir))
+            .pick_folder().await;
+
+        if let Some(result) = result {
+            if let Ok(mut config) = Config::get() {
+                config.launcher.temp = Some(result.path().to_path_buf());
+
+                if let Err(err) = Config::update_raw(config) {
+                    tracing::error!("Failed to update config: {err}");
+
+                    sender.input(AppMsg::Toast {
+                        title: tr("config-update-error"),
+                        description: Some(err.to_string())
+                    });
+                }
+            }
+        }
+
+        sender.input(AppMsg::PreviewDownload(diff));
+    }));
+}
@@ -0,0 +1,46 @@
+use relm4::{
+    prelude::*,
+    Sender
+};
+
+use gtk::glib::clone;
+
+use crate::*;
+use crate::i18n::*;
+use crate::ui::components::*;
+use crate::actions::{ActionUpdate, DownloadQueue, DownloadStatus};
+
+use super::{App, AppMsg};
+
+/// Run all the setup steps one after another until the game is ready to launch
+pub fn fix_everything(sender: ComponentSender<App>, progress_bar_input: Sender<ProgressBarMsg>, downloads: &DownloadQueue) {
+    sender.input(AppMsg::SetDownloading(true));
+
+    let updater = super::action_updater(sender.clone(), progress_bar_input.clone());
+
+    downloads.push(move |handle| actions::fix_everything(handle, updater.clone()), clone!(@strong sender => move |result| {
+        match result {
+            Ok(DownloadStatus::Finished) => sender.input(AppMsg::Toast {
+                title: tr("setup-finished"),
+                description: None
+            }),
+
+            Ok(status) => tracing::info!("Setup was interrupted: {status:?}"),
+
+            Err(err) => sender.input(AppMsg::Toast {
+                title: err.title,
+                description: err.description
+            })
+        }
+
+        #[allow(unused_must_use)] {
+            progress_bar_input.send(ProgressBarMsg::UpdateFromAction(ActionUpdate::Step(None)));
+        }
+
+        sender.input(AppMsg::SetDownloading(false));
+        sender.input(AppMsg::UpdateLauncherState {
+            perform_on_download_needed: false,
+            show_status_page: false
+        });
+    }));
+}
@@ -0,0 +1,33 @@
+use std::path::PathBuf;
+
+use relm4::{
+    prelude::*,
+    Sender
+};
+
+use crate::*;
+use crate::ui::components::*;
+
+use super::{App, AppMsg};
+
+pub fn install_archive(sender: ComponentSender<App>, progress_bar_input: Sender<ProgressBarMsg>, archive: PathBuf) {
+    sender.input(AppMsg::SetDownloading(true));
+
+    std::thread::spawn(move || {
+        let result = actions::ExpectedArchive::get()
+            .and_then(|expected| actions::install_archive(&archive, &expected, super::action_updater(sender.clone(), progress_bar_input)));
+
+        if let Err(err) = result {
+            sender.input(AppMsg::Toast {
+                title: err.title,
+                description: err.description
+            });
+        }
+
+        sender.input(AppMsg::SetDownloading(false));
+        sender.input(AppMsg::UpdateLauncherState {
+            perform_on_download_needed: false,
+            show_status_page: false
+        });
+    });
+}
@@ -1,9 +1,6 @@
 use relm4::prelude::*;
 
-use anime_launcher_sdk::anime_game_core::patches::mfc140;
-
 use crate::*;
-use crate::i18n::*;
 
 use super::{App, AppMsg};
 
@@ -11,14 +8,10 @@ pub fn install_mfc140(sender: ComponentSender<App>) {
     sender.input(AppMsg::DisableButtons(true));
 
     std::thread::spawn(move || {
-        let config = Config::get().unwrap();
-
-        if let Err(err) = mfc140::install(config.get_wine_prefix_path(), config.launcher.temp) {
-            tracing::error!("Failed to install mfc140: {err}");
-
+        if let Err(err) = actions::install_mfc140(|_| {}) {
             sender.input(AppMsg::Toast {
-                title: tr("downloading-failed"),
-                description: Some(err.to_string())
+                title: err.title,
+                description: err.description
             });
         }
 
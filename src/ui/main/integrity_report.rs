@@ -0,0 +1,179 @@
+use std::path::PathBuf;
+
+use relm4::prelude::*;
+
+use gtk::prelude::*;
+use adw::prelude::*;
+
+use gtk::glib::clone;
+
+use crate::*;
+use crate::i18n::*;
+use crate::actions::{IntegrityReport, ReportedFile};
+
+use super::{App, AppMsg, MAIN_WINDOW};
+
+/// Rows limit of each files group. Full list is available in the exported report
+const MAX_ROWS: usize = 200;
+
+fn format_size(size: Option<u64>) -> String {
+    size.map(prettify_bytes).unwrap_or_else(|| String::from("?"))
+}
+
+/// Add reported files to the group. Returns check buttons of selectable rows
+fn add_files(group: &adw::PreferencesGroup, files: &[ReportedFile], selectable: bool) -> Vec<(PathBuf, gtk::CheckButton)> {
+    let mut selection = Vec::new();
+
+    for file in files.iter().take(MAX_ROWS) {
+        let row = adw::ActionRow::new();
+
+        row.set_title(&file.path.to_string_lossy());
+
+        row.set_subtitle(&match (file.expected_size, file.actual_size) {
+            (Some(expected), Some(actual)) => tr_args("integrity-file-sizes", [
+                ("expected", prettify_bytes(expected).into()),
+                ("actual", prettify_bytes(actual).into())
+            ]),
+
+            (expected, actual) => format_size(expected.or(actual))
+        });
+
+        if selectable {
+            let check = gtk::CheckButton::new();
+
+            check.set_active(true);
+
+            row.add_prefix(&check);
+            row.set_activatable_widget(Some(&check));
+
+            selection.push((file.path.clone(), check));
+        }
+
+        group.add(&row);
+    }
+
+    if files.len() > MAX_ROWS {
+        let row = adw::ActionRow::new();
+
+        row.set_title(&tr_args("integrity-more-files", [
+            ("count", (files.len() - MAX_ROWS).into())
+        ]));
+
+        group.add(&row);
+    }
+
+    selection
+}
+
+/// Save report as JSON file picked by the user
+fn export(sender: ComponentSender<App>, report: IntegrityReport) {
+    gtk::glib::MainContext::default().spawn_local(async move {
+        let result = rfd::AsyncFileDialog::new()
+            .set_file_name("integrity-report.json")
+            .add_filter("JSON", &["json"])
+            .save_file().await;
+
+        let Some(result) = result else {
+            return;
+        };
+
+        let result = report.to_json()
+            .map_err(|err| err.to_string())
+            .and_then(|json| std::fs::write(result.path(), json).map_err(|err| err.to_string()));
+
+        if let Err(err) = result {
+            tracing::error!("Failed to export integrity report: {err}");
+
+            sender.input(AppMsg::Toast {
+                title: tr("integrity-report-exporting-error"),
+                description: Some(err)
+            });
+        }
+    });
+}
+
+/// Show mismatched, missing and extra game files found by the verification
+pub fn show(sender: ComponentSender<App>, report: IntegrityReport) {
+    let window = adw::Window::new();
+
+    window.set_title(Some(&tr("integrity-report")));
+    window.set_default_size(640, 600);
+    window.set_modal(true);
+    window.set_transient_for(unsafe { MAIN_WINDOW.as_ref() });
+
+    let page = adw::PreferencesPage::new();
+
+    let content = gtk::Box::new(gtk::Orientation::Vertical, 0);
+
+    let header = adw::HeaderBar::new();
+
+    let repair = gtk::Button::with_label(&tr("repair-selected"));
+    let export_button = gtk::Button::with_label(&tr("export-report"));
+
+    repair.add_css_class("suggested-action");
+    repair.set_sensitive(!report.is_clean());
+
+    header.pack_start(&repair);
+    header.pack_end(&export_button);
+
+    content.append(&header);
+    content.append(&page);
+
+    page.set_vexpand(true);
+
+    window.set_content(Some(&content));
+
+    let summary = adw::PreferencesGroup::new();
+
+    summary.set_description(Some(&if report.is_clean() {
+        tr("integrity-report-clean")
+    } else {
+        tr_args("integrity-report-broken", [
+            ("count", report.broken.len().into())
+        ])
+    }));
+
+    page.add(&summary);
+
+    let mut selection = Vec::new();
+
+    for (title, description, files, selectable) in [
+        (tr("mismatched-files"), tr("mismatched-files-description"), &report.mismatched, true),
+        (tr("missing-files"), tr("missing-files-description"), &report.missing, true),
+        (tr("extra-files"), tr("extra-files-description"), &report.extra, false),
+        (tr("excluded-files"), tr("excluded-files-description"), &report.excluded, false)
+    ] {
+        if files.is_empty() {
+            continue;
+        }
+
+        let group = adw::PreferencesGroup::new();
+
+        group.set_title(&format!("{title} ({})", files.len()));
+        group.set_description(Some(&description));
+
+        selection.extend(add_files(&group, files, selectable));
+
+        page.add(&group);
+    }
+
+    repair.connect_clicked(clone!(@strong sender, @strong report, @weak window => move |_| {
+        // Files which didn't fit into the rows limit are always repaired
+        let unselected = selection.iter()
+            .filter(|(_, check)| !check.is_active())
+            .map(|(path, _)| path.clone())
+            .collect::<Vec<_>>();
+
+        let files = report.broken_except(&unselected);
+
+        if !files.is_empty() {
+            sender.input(AppMsg::RepairFiles(files));
+
+            window.close();
+        }
+    }));
+
+    export_button.connect_clicked(move |_| export(sender.clone(), report.clone()));
+
+    window.present();
+}
@@ -5,10 +5,11 @@ use crate::i18n::*;
 use super::{App, AppMsg};
 
 pub fn launch(sender: ComponentSender<App>) {
+    sender.input(AppMsg::SetGameRunning(true));
     sender.input(AppMsg::HideWindow);
 
     std::thread::spawn(move || {
-        if let Err(err) = anime_launcher_sdk::pgr::game::run() {
+        if let Err(err) = crate::game::run() {
             tracing::error!("Failed to launch game: {err}");
 
             sender.input(AppMsg::Toast {
@@ -17,6 +18,7 @@ pub fn launch(sender: ComponentSender<App>) {
             });
         }
 
+        sender.input(AppMsg::SetGameRunning(false));
         sender.input(AppMsg::ShowWindow);
     });
 }
@@ -1,8 +1,13 @@
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
 use relm4::{
     prelude::*,
     component::*,
     actions::*,
-    MessageBroker
+    MessageBroker,
+    Sender
 };
 
 use gtk::prelude::*;
@@ -11,12 +16,20 @@ use adw::prelude::*;
 use gtk::glib::clone;
 
 mod repair_game;
+mod integrity_report;
 mod download_wine;
 mod create_prefix;
 mod install_mfc140;
 mod install_fonts;
 mod download_diff;
+mod install_archive;
+mod download_preview;
 mod launch;
+mod dbus;
+mod auto_update;
+mod startup;
+mod checklist;
+mod fix_everything;
 
 use anime_launcher_sdk::components::loader::ComponentsLoader;
 
@@ -31,6 +44,10 @@ use anime_launcher_sdk::pgr::consts::*;
 use crate::*;
 use crate::i18n::*;
 use crate::ui::components::*;
+use crate::actions::{ActionUpdate, DownloadQueue, IntegrityReport};
+use crate::checklist::CheckFix;
+
+use startup::StartupTask;
 
 use super::preferences::main::*;
 use super::about::*;
@@ -41,6 +58,11 @@ relm4::new_stateless_action!(LauncherFolder, WindowActionGroup, "launcher_folder
 relm4::new_stateless_action!(GameFolder, WindowActionGroup, "game_folder");
 relm4::new_stateless_action!(ConfigFile, WindowActionGroup, "config_file");
 relm4::new_stateless_action!(DebugFile, WindowActionGroup, "debug_file");
+relm4::new_stateless_action!(InstallArchive, WindowActionGroup, "install_archive");
+relm4::new_stateless_action!(SetupChecklist, WindowActionGroup, "setup_checklist");
+relm4::new_stateless_action!(FixEverything, WindowActionGroup, "fix_everything");
+relm4::new_stateless_action!(ExportManifest, WindowActionGroup, "export_manifest");
+relm4::new_stateless_action!(VerifyWithManifest, WindowActionGroup, "verify_with_manifest");
 // relm4::new_stateless_action!(WishUrl, WindowActionGroup, "wish_url");
 
 relm4::new_stateless_action!(About, WindowActionGroup, "about");
@@ -55,11 +77,40 @@ pub struct App {
     toast_overlay: adw::ToastOverlay,
 
     loading: Option<Option<String>>,
+
+    /// Names of startup tasks which are still running
+    startup_tasks: Vec<String>,
+    skip_startup_tasks: Arc<AtomicBool>,
+
     style: LauncherStyle,
     state: Option<LauncherState>,
 
+    /// Number of the latest launcher state update
+    state_generation: Arc<AtomicU64>,
+
+    /// Loading page is shown until launcher state is updated
+    state_status_page: bool,
+
+    /// Result of the game version startup task. Used by the first launcher state update
+    /// so it doesn't request the game version again after the task was timed out or skipped
+    startup_game_version: Option<startup::GameVersion>,
+
+    downloads: DownloadQueue,
+
+    /// Number of started jobs which are not finished yet. Jobs could be started
+    /// before the `SetDownloading` message of the previous one was handled
+    running_jobs: usize,
+
     downloading: bool,
-    disabled_buttons: bool
+    disabled_buttons: bool,
+
+    /// Game was launched and is still running. Game files
+    /// shouldn't be changed by automatic updates until it's closed
+    game_running: bool,
+
+    /// Show pause and cancel buttons for the current download
+    download_controls: bool,
+    download_paused: bool
 }
 
 #[derive(Debug)]
@@ -73,24 +124,100 @@ pub enum AppMsg {
         show_status_page: bool
     },
 
+    /// Launcher state evaluated in background by `AppMsg::UpdateLauncherState`
+    LauncherStateUpdated {
+        /// Number of the state update. Outdated results are dropped
+        generation: u64,
+
+        state: Result<LauncherState, String>,
+        perform_on_download_needed: bool
+    },
+
     /// Supposed to be called automatically on app's run when the latest game version
     /// was retrieved from the API
     SetGameDiff(Option<VersionDiff>),
 
+    /// Supposed to be called automatically on app's run when the game version startup task
+    /// was finished, timed out or skipped
+    SetStartupGameVersion(startup::GameVersion),
+
     /// Supposed to be called automatically on app's run when the launcher state was chosen
     SetLauncherState(Option<LauncherState>),
 
     SetLauncherStyle(LauncherStyle),
     SetLoadingStatus(Option<Option<String>>),
 
+    SetStartupTasks(Vec<String>),
+
+    /// Stop waiting for running startup tasks
+    SkipStartupTasks,
+
     SetDownloading(bool),
     DisableButtons(bool),
 
+    PauseDownload,
+    ResumeDownload,
+    ToggleDownloadPause,
+    CancelDownload,
+
     OpenPreferences,
     RepairGame,
 
+    /// Verify game files and show the report without repairing them
+    VerifyGame,
+
+    ShowIntegrityReport(IntegrityReport),
+
+    /// Repair game files selected in the integrity report
+    RepairFiles(Vec<repairer::IntegrityFile>),
+
+    /// Save official integrity files list to the local manifest
+    ExportManifest,
+
+    /// Pick local integrity manifest to verify game files against
+    ChooseManifest,
+
+    /// Verify game files against the local integrity manifest
+    VerifyWithManifest(PathBuf),
+
+    /// Show every launcher setup check with its status
+    ShowChecklist,
+
+    /// Run fix action from the setup checklist
+    RunCheckFix(CheckFix),
+
+    /// Run all the setup steps one after another
+    FixEverything,
+
+    /// Pick local game archive to install
+    ChooseArchive,
+
+    /// Install game or its update from the local archive
+    InstallArchive(PathBuf),
+
     PerformAction,
 
+    /// Show download size and free space preview before downloading the diff
+    PreviewDownload(VersionDiff),
+
+    /// Change temp folder from the download preview
+    ChooseTempFolder(VersionDiff),
+
+    DownloadDiff(VersionDiff),
+
+    /// Look for game updates if the launcher isn't busy
+    CheckAutoUpdate,
+
+    /// Download game update without user interaction
+    AutoUpdate(VersionDiff),
+
+    /// Launch the game if it's ready, or show the window otherwise.
+    /// Sent when another launcher instance forwards `--run-game` request
+    RunGame,
+
+    /// Sent when the game is launched and when it's closed
+    SetGameRunning(bool),
+
     HideWindow,
     ShowWindow,
 
@@ -115,6 +242,17 @@ impl SimpleComponent for App {
                 &tr("debug-file") => DebugFile,
             },
 
+            section! {
+                &tr("setup-checklist") => SetupChecklist,
+                &tr("fix-everything") => FixEverything,
+                &tr("install-from-archive") => InstallArchive
+            },
+
+            section! {
+                &tr("export-integrity-manifest") => ExportManifest,
+                &tr("verify-with-manifest") => VerifyWithManifest
+            },
+
             /*section! {
                 &tr("wish-url") => WishUrl
             },*/
@@ -200,7 +338,33 @@ impl SimpleComponent for App {
                         },
 
                         #[watch]
-                        set_visible: model.loading.is_some()
+                        set_visible: model.loading.is_some(),
+
+                        #[wrap(Some)]
+                        set_child = &gtk::Box {
+                            set_orientation: gtk::Orientation::Vertical,
+                            set_halign: gtk::Align::Center,
+                            set_spacing: 12,
+
+                            #[watch]
+                            set_visible: !model.startup_tasks.is_empty(),
+
+                            gtk::Label {
+                                add_css_class: "dim-label",
+                                set_justify: gtk::Justification::Center,
+
+                                #[watch]
+                                set_label: &model.startup_tasks.join("\n")
+                            },
+
+                            gtk::Button {
+                                set_label: &tr("skip"),
+                                set_halign: gtk::Align::Center,
+                                add_css_class: "pill",
+
+                                connect_clicked => AppMsg::SkipStartupTasks
+                            }
+                        }
                     },
 
                     adw::PreferencesPage {
@@ -246,7 +410,50 @@ impl SimpleComponent for App {
                             set_margin_top: 48,
                             set_margin_bottom: 48,
 
-                            add = model.progress_bar.widget(),
+                            gtk::Box {
+                                set_orientation: gtk::Orientation::Horizontal,
+                                set_halign: gtk::Align::Center,
+                                set_spacing: 8,
+
+                                append = model.progress_bar.widget(),
+
+                                gtk::Button {
+                                    #[watch]
+                                    set_visible: model.download_controls,
+
+                                    #[watch]
+                                    set_icon_name: if model.download_paused {
+                                        "media-playback-start-symbolic"
+                                    } else {
+                                        "media-playback-pause-symbolic"
+                                    },
+
+                                    #[watch]
+                                    set_tooltip_text: Some(&if model.download_paused {
+                                        tr("resume")
+                                    } else {
+                                        tr("pause")
+                                    }),
+
+                                    set_valign: gtk::Align::Center,
+                                    add_css_class: "circular",
+
+                                    connect_clicked => AppMsg::ToggleDownloadPause
+                                },
+
+                                gtk::Button {
+                                    #[watch]
+                                    set_visible: model.download_controls,
+
+                                    set_icon_name: "process-stop-symbolic",
+                                    set_tooltip_text: Some(&tr("cancel")),
+
+                                    set_valign: gtk::Align::Center,
+                                    add_css_class: "circular",
+
+                                    connect_clicked => AppMsg::CancelDownload
+                                }
+                            }
                         },
 
                         add = &adw::PreferencesGroup {
@@ -414,11 +621,29 @@ impl SimpleComponent for App {
             toast_overlay: adw::ToastOverlay::new(),
 
             loading: Some(None),
+
+            startup_tasks: Vec::new(),
+            skip_startup_tasks: Arc::new(AtomicBool::new(false)),
+
             style: CONFIG.launcher.style,
             state: None,
 
+            state_generation: Arc::new(AtomicU64::new(0)),
+            state_status_page: false,
+
+            startup_game_version: None,
+
+            downloads: DownloadQueue::new(),
+
+            running_jobs: 0,
+
             downloading: false,
-            disabled_buttons: false
+            disabled_buttons: false,
+
+            game_running: false,
+
+            download_controls: false,
+            download_paused: false
         };
 
         model.progress_bar.widget().set_halign(gtk::Align::Center);
@@ -558,26 +783,112 @@ impl SimpleComponent for App {
             }));
         })));*/
 
+        group.add_action::<InstallArchive>(RelmAction::new_stateless(clone!(@strong sender => move |_| {
+            sender.input(AppMsg::ChooseArchive);
+        })));
+
+        group.add_action::<SetupChecklist>(RelmAction::new_stateless(clone!(@strong sender => move |_| {
+            sender.input(AppMsg::ShowChecklist);
+        })));
+
+        group.add_action::<FixEverything>(RelmAction::new_stateless(clone!(@strong sender => move |_| {
+            sender.input(AppMsg::FixEverything);
+        })));
+
+        group.add_action::<ExportManifest>(RelmAction::new_stateless(clone!(@strong sender => move |_| {
+            sender.input(AppMsg::ExportManifest);
+        })));
+
+        group.add_action::<VerifyWithManifest>(RelmAction::new_stateless(clone!(@strong sender => move |_| {
+            sender.input(AppMsg::ChooseManifest);
+        })));
+
         group.add_action::<About>(RelmAction::new_stateless(move |_| {
             about_dialog_broker.send(AboutDialogMsg::Show);
         }));
 
         widgets.main_window.insert_action_group("win", Some(&group.into_action_group()));
 
+        // Install game archives dropped onto the window
+        let drop_target = gtk::DropTarget::new(gtk::gio::File::static_type(), gtk::gdk::DragAction::COPY);
+
+        drop_target.connect_drop(clone!(@strong sender => move |_, value, _, _| {
+            match value.get::<gtk::gio::File>().map(|file| file.path()) {
+                Ok(Some(path)) => {
+                    sender.input(AppMsg::InstallArchive(path));
+
+                    true
+                }
+
+                _ => false
+            }
+        }));
+
+        widgets.main_window.add_controller(&drop_target);
+
+        // Handle `--run-game` requests forwarded by another launcher instances
+        let run_game = gtk::gio::SimpleAction::new("run-game", None);
+
+        run_game.connect_activate(clone!(@strong sender => move |_, _| {
+            sender.input(AppMsg::RunGame);
+        }));
+
+        relm4::main_application().add_action(&run_game);
+
+        // Check for game updates in background if enabled
+        auto_update::start(sender.clone());
+
+        // Expose launcher controls on the session bus
+        if let Err(err) = dbus::register(sender.clone()) {
+            tracing::error!("Failed to register D-Bus control interface: {err}");
+        }
+
         tracing::info!("Main window initialized");
 
         let download_picture = model.style == LauncherStyle::Classic && !KEEP_BACKGROUND_FILE.exists();
 
+        let skip_startup_tasks = model.skip_startup_tasks.clone();
+
         // Initialize some heavy tasks
         std::thread::spawn(move || {
             tracing::info!("Initializing heavy tasks");
 
+            // Switch to offline mode if the network is unavailable
+
+            if !crate::offline::is_offline() {
+                let online = Arc::new(AtomicBool::new(false));
+
+                startup::run(&sender, vec![
+                    StartupTask::new("startup-task-network", startup::NETWORK_CHECK_TIMEOUT, clone!(@strong online => move || {
+                        online.store(!crate::offline::detect(), Ordering::Relaxed);
+                    }))
+                ], skip_startup_tasks.clone());
+
+                // Network check also fails if it timed out or was skipped
+                if !online.load(Ordering::Relaxed) {
+                    tracing::warn!("Network is unavailable. Switching to offline mode");
+
+                    crate::offline::set_offline(true);
+                }
+            }
+
+            let offline = crate::offline::is_offline();
+
+            if offline {
+                sender.input(AppMsg::Toast {
+                    title: tr("offline-mode"),
+                    description: Some(tr("offline-mode-description"))
+                });
+            }
+
             let mut tasks = Vec::new();
 
+            let game_diff = Arc::new(Mutex::new(None));
+
             // Download background picture if needed
 
-            if download_picture {
-                tasks.push(std::thread::spawn(clone!(@strong sender => move || {
+            if download_picture && !offline {
+                tasks.push(StartupTask::new("startup-task-background", startup::BACKGROUND_TIMEOUT, clone!(@strong sender => move || {
                     if let Err(err) = crate::background::download_background() {
                         tracing::error!("Failed to download background picture: {err}");
 
@@ -591,77 +902,93 @@ impl SimpleComponent for App {
 
             // Update components index
 
-            tasks.push(std::thread::spawn(clone!(@strong sender => move || {
-                let components = ComponentsLoader::new(&CONFIG.components.path);
-
-                match components.is_sync(&CONFIG.components.servers) {
-                    Ok(Some(_)) => (),
-
-                    Ok(None) => {
-                        for host in &CONFIG.components.servers {
-                            match components.sync(host) {
-                                Ok(changes) => {
-                                    sender.input(AppMsg::Toast {
-                                        title: tr("components-index-updated"),
-                                        description: if changes.is_empty() {
-                                            None
-                                        } else {
-                                            Some(changes.into_iter()
-                                                .map(|line| format!("- {line}"))
-                                                .collect::<Vec<_>>()
-                                                .join("\n"))
-                                        }
-                                    });
+            if !offline {
+                tasks.push(StartupTask::new("startup-task-components", startup::COMPONENTS_TIMEOUT, clone!(@strong sender => move || {
+                    let components = ComponentsLoader::new(&CONFIG.components.path);
+
+                    match components.is_sync(&CONFIG.components.servers) {
+                        Ok(Some(_)) => (),
+
+                        Ok(None) => {
+                            for host in &CONFIG.components.servers {
+                                match components.sync(host) {
+                                    Ok(changes) => {
+                                        sender.input(AppMsg::Toast {
+                                            title: tr("components-index-updated"),
+                                            description: if changes.is_empty() {
+                                                None
+                                            } else {
+                                                Some(changes.into_iter()
+                                                    .map(|line| format!("- {line}"))
+                                                    .collect::<Vec<_>>()
+                                                    .join("\n"))
+                                            }
+                                        });
 
-                                    break;
-                                }
+                                        break;
+                                    }
 
-                                Err(err) => {
-                                    tracing::error!("Failed to sync components index");
+                                    Err(err) => {
+                                        tracing::error!("Failed to sync components index");
 
-                                    sender.input(AppMsg::Toast {
-                                        title: tr("components-index-sync-failed"),
-                                        description: Some(err.to_string())
-                                    });
+                                        sender.input(AppMsg::Toast {
+                                            title: tr("components-index-sync-failed"),
+                                            description: Some(err.to_string())
+                                        });
+                                    }
                                 }
                             }
                         }
-                    }
 
-                    Err(err) => {
-                        tracing::error!("Failed to verify that components index synced");
+                        Err(err) => {
+                            tracing::error!("Failed to verify that components index synced");
 
-                        sender.input(AppMsg::Toast {
-                            title: tr("components-index-verify-failed"),
-                            description: Some(err.to_string())
-                        });
+                            sender.input(AppMsg::Toast {
+                                title: tr("components-index-verify-failed"),
+                                description: Some(err.to_string())
+                            });
+                        }
                     }
-                }
-            })));
+                })));
+            }
 
             // Update initial game version status
 
-            tasks.push(std::thread::spawn(clone!(@strong sender => move || {
-                sender.input(AppMsg::SetGameDiff(match GAME.try_get_diff() {
-                    Ok(diff) => Some(diff),
-                    Err(err) => {
-                        tracing::error!("Failed to find game diff: {err}");
+            if !offline {
+                tasks.push(StartupTask::new("startup-task-game-version", startup::GAME_DIFF_TIMEOUT, clone!(@strong sender, @strong game_diff => move || {
+                    sender.input(AppMsg::SetGameDiff(match GAME.try_get_diff() {
+                        Ok(diff) => {
+                            crate::actions::ExpectedArchive::remember(&diff);
 
-                        sender.input(AppMsg::Toast {
-                            title: tr("game-diff-finding-error"),
-                            description: Some(err.to_string())
-                        });
+                            *game_diff.lock().unwrap() = Some(diff.clone());
 
-                        None
-                    }
-                }));
+                            Some(diff)
+                        }
+
+                        Err(err) => {
+                            tracing::error!("Failed to find game diff: {err}");
+
+                            sender.input(AppMsg::Toast {
+                                title: tr("game-diff-finding-error"),
+                                description: Some(err.to_string())
+                            });
+
+                            None
+                        }
+                    }));
 
-                tracing::info!("Updated game version status");
-            })));
+                    tracing::info!("Updated game version status");
+                })));
+            }
+
+            // Await for tasks to finish execution, time out or be skipped
+            startup::run(&sender, tasks, skip_startup_tasks);
 
-            // Await for tasks to finish execution
-            for task in tasks {
-                task.join().expect("Failed to join task");
+            if !offline {
+                sender.input(AppMsg::SetStartupGameVersion(match game_diff.lock().unwrap().take() {
+                    Some(diff) => startup::GameVersion::Found(diff),
+                    None => startup::GameVersion::Unknown
+                }));
             }
 
             // Update launcher state
@@ -686,13 +1013,19 @@ impl SimpleComponent for App {
             // TODO: make function from this message like with toast
             AppMsg::UpdateLauncherState { perform_on_download_needed, show_status_page } => {
                 if show_status_page {
+                    self.state_status_page = true;
+
                     sender.input(AppMsg::SetLoadingStatus(Some(Some(tr("loading-launcher-state")))));
                 } else {
                     self.disabled_buttons = true;
                 }
 
-                let updater = clone!(@strong sender => move |state| {
-                    if show_status_page {
+                // Results of previous updates are dropped when they're finished
+                let generation = self.state_generation.fetch_add(1, Ordering::Relaxed) + 1;
+                let current_generation = self.state_generation.clone();
+
+                let updater = clone!(@strong sender, @strong current_generation => move |state| {
+                    if show_status_page && current_generation.load(Ordering::Relaxed) == generation {
                         match state {
                             StateUpdating::Components => {
                                 sender.input(AppMsg::SetLoadingStatus(Some(Some(tr("loading-launcher-state--components")))));
@@ -705,30 +1038,70 @@ impl SimpleComponent for App {
                     }
                 });
 
-                let state = match LauncherState::get_from_config(updater) {
+                let startup_game_version = self.startup_game_version.take();
+
+                // Launcher state evaluation checks components, prefix and game files
+                // so it's done in background to not to freeze the window
+                std::thread::spawn(clone!(@strong sender => move || {
+                    let state = match startup_game_version {
+                        // Reuse game version found on the launcher start
+                        Some(startup::GameVersion::Found(diff)) => crate::offline::local_state_with_diff(Some(diff)),
+
+                        // Game version request already took too long, so it's not repeated
+                        Some(startup::GameVersion::Unknown) => {
+                            tracing::warn!("Game version is unknown. Using locally installed game version");
+
+                            crate::offline::local_state()
+                        }
+
+                        None if crate::offline::is_offline() => crate::offline::local_state(),
+
+                        // Remember required game archive so it could be installed offline later
+                        None => LauncherState::get_from_config(updater)
+                            .inspect(crate::actions::ExpectedArchive::remember_state)
+                    };
+
+                    sender.input(AppMsg::LauncherStateUpdated {
+                        generation,
+                        state: state.map_err(|err| err.to_string()),
+                        perform_on_download_needed
+                    });
+                }));
+            }
+
+            AppMsg::LauncherStateUpdated { generation, state, perform_on_download_needed } => {
+                if generation != self.state_generation.load(Ordering::Relaxed) {
+                    tracing::debug!("Dropped outdated launcher state: {state:?}");
+
+                    return;
+                }
+
+                let state = match state {
                     Ok(state) => Some(state),
                     Err(err) => {
                         tracing::error!("Failed to update launcher state: {err}");
 
-                        self.toast(tr("launcher-state-updating-error"), Some(err.to_string()));
-    
+                        self.toast(tr("launcher-state-updating-error"), Some(err));
+
                         None
                     }
                 };
 
                 sender.input(AppMsg::SetLauncherState(state.clone()));
 
-                if show_status_page {
+                if self.state_status_page {
+                    self.state_status_page = false;
+
                     sender.input(AppMsg::SetLoadingStatus(None));
-                } else {
-                    self.disabled_buttons = false;
                 }
 
+                self.disabled_buttons = false;
+
                 if let Some(state) = state {
                     match state {
-                        LauncherState::GameUpdateAvailable(_) |
-                        LauncherState::GameNotInstalled(_) if perform_on_download_needed => {
-                            sender.input(AppMsg::PerformAction);
+                        LauncherState::GameUpdateAvailable(diff) |
+                        LauncherState::GameNotInstalled(diff) if perform_on_download_needed => {
+                            sender.input(AppMsg::DownloadDiff(diff));
                         }
 
                         _ => ()
@@ -736,12 +1109,16 @@ impl SimpleComponent for App {
                 }
             }
 
+            AppMsg::SetStartupGameVersion(game_version) => self.startup_game_version = Some(game_version),
+
             #[allow(unused_must_use)]
             AppMsg::SetGameDiff(diff) => unsafe {
                 PREFERENCES_WINDOW.as_ref().unwrap_unchecked().sender().send(PreferencesAppMsg::SetGameDiff(diff));
             }
 
             AppMsg::SetLauncherState(state) => {
+                dbus::set_state(state.as_ref());
+
                 self.state = state;
             }
 
@@ -749,29 +1126,191 @@ impl SimpleComponent for App {
                 self.loading = status;
             }
 
+            AppMsg::SetStartupTasks(tasks) => {
+                self.startup_tasks = tasks;
+            }
+
+            AppMsg::SkipStartupTasks => {
+                self.skip_startup_tasks.store(true, Ordering::Relaxed);
+            }
+
             AppMsg::SetLauncherStyle(style) => {
                 self.style = style;
             }
 
             AppMsg::SetDownloading(state) => {
-                self.downloading = state;
+                if state {
+                    self.running_jobs += 1;
+                } else {
+                    self.running_jobs = self.running_jobs.saturating_sub(1);
+                }
+
+                // Keep download controls until all the queued jobs are finished
+                self.downloading = self.running_jobs > 0;
+
+                dbus::set_downloading(self.downloading);
+
+                if !self.downloading {
+                    self.download_controls = false;
+                    self.download_paused = false;
+                }
             }
 
             AppMsg::DisableButtons(state) => {
                 self.disabled_buttons = state;
             }
 
+            AppMsg::PauseDownload => {
+                if self.download_controls && !self.download_paused {
+                    self.download_paused = true;
+
+                    self.downloads.pause();
+
+                    #[allow(unused_must_use)] {
+                        self.progress_bar.sender().send(ProgressBarMsg::UpdateCaption(Some(tr("paused"))));
+                    }
+                }
+            }
+
+            AppMsg::ResumeDownload => {
+                if self.download_paused {
+                    self.download_paused = false;
+
+                    self.downloads.resume();
+                }
+            }
+
+            AppMsg::ToggleDownloadPause => {
+                if self.download_paused {
+                    sender.input(AppMsg::ResumeDownload);
+                } else {
+                    sender.input(AppMsg::PauseDownload);
+                }
+            }
+
+            AppMsg::CancelDownload => {
+                if self.download_controls {
+                    self.download_paused = false;
+
+                    self.downloads.cancel();
+
+                    #[allow(unused_must_use)] {
+                        self.progress_bar.sender().send(ProgressBarMsg::UpdateCaption(Some(tr("cancelling"))));
+                    }
+                }
+            }
+
             AppMsg::OpenPreferences => unsafe {
                 PREFERENCES_WINDOW.as_ref().unwrap_unchecked().widget().present();
             }
 
-            AppMsg::RepairGame => repair_game::repair_game(sender, self.progress_bar.sender().to_owned()),
+            AppMsg::RepairGame => {
+                if self.downloading || self.disabled_buttons {
+                    self.toast(tr("launcher-busy"), None);
+                }
+
+                else {
+                    self.download_controls = true;
+
+                    repair_game::repair_game(sender, self.progress_bar.sender().to_owned(), &self.downloads);
+                }
+            }
+
+            AppMsg::VerifyGame => {
+                if self.downloading || self.disabled_buttons {
+                    self.toast(tr("launcher-busy"), None);
+                }
+
+                else {
+                    self.download_controls = true;
+
+                    repair_game::verify_game(sender, self.progress_bar.sender().to_owned(), &self.downloads);
+                }
+            }
+
+            AppMsg::ShowIntegrityReport(report) => integrity_report::show(sender, report),
+
+            AppMsg::RepairFiles(files) => {
+                if self.downloading || self.disabled_buttons {
+                    self.toast(tr("launcher-busy"), None);
+                }
+
+                else {
+                    self.download_controls = true;
+
+                    repair_game::repair_files(sender, self.progress_bar.sender().to_owned(), &self.downloads, files);
+                }
+            }
+
+            AppMsg::ExportManifest => repair_game::export_manifest(sender),
+
+            AppMsg::ChooseManifest => {
+                gtk::glib::MainContext::default().spawn_local(clone!(@strong sender => async move {
+                    let result = rfd::AsyncFileDialog::new()
+                        .add_filter("JSON", &["json"])
+                        .pick_file().await;
+
+                    if let Some(result) = result {
+                        sender.input(AppMsg::VerifyWithManifest(result.path().to_path_buf()));
+                    }
+                }));
+            }
+
+            AppMsg::VerifyWithManifest(manifest) => {
+                if self.downloading || self.disabled_buttons {
+                    self.toast(tr("launcher-busy"), None);
+                }
+
+                else {
+                    self.download_controls = true;
+
+                    repair_game::verify_with_manifest(sender, self.progress_bar.sender().to_owned(), &self.downloads, manifest);
+                }
+            }
+
+            AppMsg::ChooseArchive => {
+                gtk::glib::MainContext::default().spawn_local(clone!(@strong sender => async move {
+                    let result = rfd::AsyncFileDialog::new()
+                        .add_filter(&tr("game-archive"), &["zip", "7z", "tar", "gz", "xz", "zst"])
+                        .pick_file().await;
+
+                    if let Some(result) = result {
+                        sender.input(AppMsg::InstallArchive(result.path().to_path_buf()));
+                    }
+                }));
+            }
 
-            AppMsg::PerformAction => unsafe {
-                match self.state.as_ref().unwrap_unchecked() {
+            AppMsg::InstallArchive(archive) => {
+                if self.downloading || self.disabled_buttons {
+                    self.toast(tr("launcher-busy"), None);
+                }
+
+                else {
+                    install_archive::install_archive(sender, self.progress_bar.sender().to_owned(), archive);
+                }
+            }
+
+            AppMsg::PerformAction => {
+                if self.downloading || self.disabled_buttons || self.game_running {
+                    self.toast(tr("launcher-busy"), None);
+
+                    return;
+                }
+
+                // Action can be requested over D-Bus while the state is not loaded yet
+                let Some(state) = &self.state else {
+                    return;
+                };
+
+                match state {
                     LauncherState::Launch => launch::launch(sender),
 
-                    LauncherState::WineNotInstalled => download_wine::download_wine(sender, self.progress_bar.sender().to_owned()),
+                    LauncherState::WineNotInstalled => {
+                        self.download_controls = true;
+
+                        download_wine::download_wine(sender, self.progress_bar.sender().to_owned(), &self.downloads);
+                    }
+
                     LauncherState::PrefixNotExists  => create_prefix::create_prefix(sender),
 
                     LauncherState::Mfc140NotInstalled => install_mfc140::install_mfc140(sender),
@@ -779,11 +1318,96 @@ impl SimpleComponent for App {
                         install_fonts::install_fonts(sender, self.progress_bar.sender().to_owned(), fonts.clone()),
 
                     LauncherState::GameUpdateAvailable(diff) |
-                    LauncherState::GameNotInstalled(diff) =>
-                        download_diff::download_diff(sender, self.progress_bar.sender().to_owned(), diff.to_owned())
+                    LauncherState::GameNotInstalled(diff) => download_preview::show(sender, diff.to_owned())
+                }
+            }
+
+            AppMsg::ShowChecklist => checklist::show(sender),
+
+            AppMsg::RunCheckFix(fix) => {
+                if self.downloading || self.disabled_buttons {
+                    self.toast(tr("launcher-busy"), None);
+
+                    return;
+                }
+
+                match fix {
+                    CheckFix::DownloadWine => {
+                        self.download_controls = true;
+
+                        download_wine::download_wine(sender, self.progress_bar.sender().to_owned(), &self.downloads);
+                    }
+
+                    CheckFix::CreatePrefix  => create_prefix::create_prefix(sender),
+                    CheckFix::InstallMfc140 => install_mfc140::install_mfc140(sender),
+
+                    CheckFix::InstallFonts(fonts) =>
+                        install_fonts::install_fonts(sender, self.progress_bar.sender().to_owned(), fonts),
+
+                    CheckFix::DownloadDiff(diff) => download_preview::show(sender, diff),
+
+                    CheckFix::OpenPreferences => sender.input(AppMsg::OpenPreferences)
                 }
             }
 
+            AppMsg::FixEverything => {
+                if self.downloading || self.disabled_buttons {
+                    self.toast(tr("launcher-busy"), None);
+                }
+
+                else if let Some(LauncherState::Launch) = self.state {
+                    self.toast(tr("setup-finished"), None);
+                }
+
+                else {
+                    self.download_controls = true;
+
+                    fix_everything::fix_everything(sender, self.progress_bar.sender().to_owned(), &self.downloads);
+                }
+            }
+
+            AppMsg::PreviewDownload(diff) => download_preview::show(sender, diff),
+            AppMsg::ChooseTempFolder(diff) => download_preview::choose_temp_folder(sender, diff),
+
+            AppMsg::DownloadDiff(diff) => {
+                if !self.downloading && !self.disabled_buttons {
+                    self.download_controls = true;
+
+                    download_diff::download_diff(sender, self.progress_bar.sender().to_owned(), &self.downloads, diff);
+                }
+            }
+
+            AppMsg::CheckAutoUpdate => {
+                let ready = matches!(self.state, Some(LauncherState::Launch) | Some(LauncherState::GameUpdateAvailable(_)));
+
+                if ready && !self.downloading && !self.disabled_buttons && !self.game_running {
+                    auto_update::check(sender);
+                }
+            }
+
+            AppMsg::AutoUpdate(diff) => {
+                // Game could be launched while we were looking for updates
+                if !self.downloading && !self.disabled_buttons && !self.game_running {
+                    self.download_controls = true;
+
+                    auto_update::auto_update(sender, self.progress_bar.sender().to_owned(), &self.downloads, diff);
+                }
+            }
+
+            AppMsg::RunGame => {
+                match &self.state {
+                    _ if self.game_running => tracing::info!("Game is already running"),
+
+                    Some(LauncherState::Launch) if !self.disabled_buttons && !self.downloading => launch::launch(sender),
+
+                    _ => sender.input(AppMsg::ShowWindow)
+                }
+            }
+
+            AppMsg::SetGameRunning(running) => {
+                self.game_running = running;
+            }
+
             AppMsg::HideWindow => unsafe {
                 MAIN_WINDOW.as_ref().unwrap_unchecked().set_visible(false);
             }
@@ -831,3 +1455,37 @@ impl App {
         self.toast_overlay.add_toast(toast);
     }
 }
+
+/// Get launcher actions updates handler which forwards their progress
+/// to the given progress bar and shows their errors and notices as toasts
+fn action_updater(sender: ComponentSender<App>, progress_bar_input: Sender<ProgressBarMsg>) -> impl Fn(ActionUpdate) + Clone + Send + 'static {
+    move |update| {
+        if let ActionUpdate::Error(err) = update {
+            sender.input(AppMsg::Toast {
+                title: err.title,
+                description: err.description
+            });
+        }
+
+        else if let ActionUpdate::Notice { title, description } = update {
+            sender.input(AppMsg::Toast {
+                title,
+                description
+            });
+        }
+
+        else {
+            match &update {
+                ActionUpdate::Progress(curr, total) |
+                ActionUpdate::Installer(InstallerUpdate::DownloadingProgress(curr, total)) |
+                ActionUpdate::Installer(InstallerUpdate::UnpackingProgress(curr, total)) => dbus::set_progress(*curr, *total),
+
+                _ => ()
+            }
+
+            #[allow(unused_must_use)] {
+                progress_bar_input.send(ProgressBarMsg::UpdateFromAction(update));
+            }
+        }
+    }
+}
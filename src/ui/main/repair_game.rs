@@ -1,3 +1,5 @@
+use std::path::PathBuf;
+
 use relm4::{
     prelude::*,
     Sender
@@ -8,121 +10,115 @@ use gtk::glib::clone;
 use crate::*;
 use crate::i18n::*;
 use crate::ui::components::*;
+use crate::actions::{DownloadQueue, DownloadStatus, ActionError};
 
 use super::{App, AppMsg};
 
-#[allow(unused_must_use)]
-pub fn repair_game(sender: ComponentSender<App>, progress_bar_input: Sender<ProgressBarMsg>) {
-    let config = Config::get().unwrap();
-
-    progress_bar_input.send(ProgressBarMsg::UpdateCaption(Some(tr("verifying-files"))));
-    sender.input(AppMsg::SetDownloading(true));
-
-    std::thread::spawn(move || {
-        match repairer::try_get_integrity_files() {
-            Ok(files) => {
-                let game_path = config.game.path;
-
-                progress_bar_input.send(ProgressBarMsg::UpdateProgress(0, 0));
-
-                let mut total = 0;
-
-                for file in &files {
-                    total += file.size;
-                }
-
-                let median_size = total / config.launcher.repairer.threads;
-                let mut i = 0;
-
-                let (verify_sender, verify_receiver) = std::sync::mpsc::channel();
-
-                for _ in 0..config.launcher.repairer.threads {
-                    let mut thread_files = Vec::new();
-                    let mut thread_files_size = 0;
-
-                    while i < files.len() {
-                        thread_files.push(files[i].clone());
-
-                        thread_files_size += files[i].size;
-                        i += 1;
-
-                        if thread_files_size >= median_size {
-                            break;
-                        }
-                    }
+/// Get callback which finishes repair job
+fn finish(sender: ComponentSender<App>) -> impl FnOnce(Result<DownloadStatus, ActionError>) + Send + 'static {
+    move |result| {
+        match result {
+            Ok(DownloadStatus::Cancelled) => sender.input(AppMsg::Toast {
+                title: tr("repair-cancelled"),
+                description: None
+            }),
+
+            Err(err) => sender.input(AppMsg::Toast {
+                title: err.title,
+                description: err.description
+            }),
+
+            _ => ()
+        }
 
-                    let thread_sender = verify_sender.clone();
+        sender.input(AppMsg::SetDownloading(false));
+    }
+}
 
-                    std::thread::spawn(clone!(@strong game_path => move || {
-                        for file in thread_files {
-                            let status = if config.launcher.repairer.fast {
-                                file.fast_verify(&game_path)
-                            } else {
-                                file.verify(&game_path)
-                            };
+pub fn repair_game(sender: ComponentSender<App>, progress_bar_input: Sender<ProgressBarMsg>, downloads: &DownloadQueue) {
+    sender.input(AppMsg::SetDownloading(true));
 
-                            thread_sender.send((file, status)).unwrap();
-                        }
-                    }));
-                }
+    let updater = super::action_updater(sender.clone(), progress_bar_input);
 
-                // We have [config.launcher.repairer.threads] copies of this sender + the original one
-                // receiver will return Err when all the senders will be dropped.
-                // [config.launcher.repairer.threads] senders will be dropped when threads will finish verifying files
-                // but this one will live as long as current thread exists so we should drop it manually
-                drop(verify_sender);
+    downloads.push(move |handle| actions::repair_game(handle, updater.clone()), finish(sender));
+}
 
-                let mut broken = Vec::new();
-                let mut processed = 0;
+/// Verify game files and show the report without repairing them
+pub fn verify_game(sender: ComponentSender<App>, progress_bar_input: Sender<ProgressBarMsg>, downloads: &DownloadQueue) {
+    sender.input(AppMsg::SetDownloading(true));
 
-                while let Ok((file, status)) = verify_receiver.recv() {
-                    processed += file.size;
+    let updater = super::action_updater(sender.clone(), progress_bar_input);
 
-                    if !status {
-                        broken.push(file);
-                    }
+    downloads.push(clone!(@strong sender => move |handle| {
+        match actions::verify_game(handle, updater.clone())? {
+            Some(report) => {
+                sender.input(AppMsg::ShowIntegrityReport(report));
 
-                    progress_bar_input.send(ProgressBarMsg::UpdateProgress(processed, total));
-                }
+                Ok(DownloadStatus::Finished)
+            }
 
-                if !broken.is_empty() {
-                    let total = broken.len() as u64;
+            None => Ok(handle.interruption().unwrap_or(DownloadStatus::Cancelled))
+        }
+    }), finish(sender));
+}
 
-                    progress_bar_input.send(ProgressBarMsg::UpdateCaption(Some(tr("repairing-files"))));
-                    progress_bar_input.send(ProgressBarMsg::DisplayFraction(false));
-                    progress_bar_input.send(ProgressBarMsg::UpdateProgress(0, total));
+/// Repair game files selected in the integrity report
+pub fn repair_files(sender: ComponentSender<App>, progress_bar_input: Sender<ProgressBarMsg>, downloads: &DownloadQueue, files: Vec<repairer::IntegrityFile>) {
+    sender.input(AppMsg::SetDownloading(true));
 
-                    tracing::warn!("Found broken files:\n{}", broken.iter().fold(String::new(), |acc, file| acc + &format!("- {}\n", file.path.to_string_lossy())));
+    let updater = super::action_updater(sender.clone(), progress_bar_input);
 
-                    for (i, file) in broken.into_iter().enumerate() {
-                        tracing::debug!("Repairing file: {}", file.path.to_string_lossy());
+    downloads.push(move |handle| actions::repair_files(files.clone(), handle, updater.clone()), finish(sender));
+}
 
-                        if let Err(err) = file.repair(&game_path) {
-                            sender.input(AppMsg::Toast {
-                                title: tr("game-file-repairing-error"),
-                                description: Some(err.to_string())
-                            });
+/// Verify game files against the local integrity manifest and show the report
+pub fn verify_with_manifest(sender: ComponentSender<App>, progress_bar_input: Sender<ProgressBarMsg>, downloads: &DownloadQueue, manifest: PathBuf) {
+    sender.input(AppMsg::SetDownloading(true));
 
-                            tracing::error!("Failed to repair game file: {err}");
-                        }
+    let updater = super::action_updater(sender.clone(), progress_bar_input);
 
-                        progress_bar_input.send(ProgressBarMsg::UpdateProgress(i as u64 + 1, total));
-                    }
+    downloads.push(clone!(@strong sender => move |handle| {
+        match actions::verify_with_manifest(&manifest, handle, updater.clone())? {
+            Some(report) => {
+                sender.input(AppMsg::ShowIntegrityReport(report));
 
-                    progress_bar_input.send(ProgressBarMsg::DisplayFraction(true));
-                }
+                Ok(DownloadStatus::Finished)
             }
 
-            Err(err) => {
-                tracing::error!("Failed to get inregrity failes: {err}");
-
-                sender.input(AppMsg::Toast {
-                    title: tr("integrity-files-getting-error"),
-                    description: Some(err.to_string())
-                });
-            }
+            None => Ok(handle.interruption().unwrap_or(DownloadStatus::Cancelled))
         }
+    }), finish(sender));
+}
 
-        sender.input(AppMsg::SetDownloading(false));
+/// Pick file and save official integrity files list to it
+pub fn export_manifest(sender: ComponentSender<App>) {
+    gtk::glib::MainContext::default().spawn_local(async move {
+        let result = rfd::AsyncFileDialog::new()
+            .set_file_name("integrity-manifest.json")
+            .add_filter("JSON", &["json"])
+            .save_file().await;
+
+        let Some(result) = result else {
+            return;
+        };
+
+        let path = result.path().to_path_buf();
+
+        // Integrity files are requested from the network
+        std::thread::spawn(move || {
+            match actions::export_manifest(&path) {
+                Ok(files) => sender.input(AppMsg::Toast {
+                    title: tr_args("manifest-exported", [
+                        ("files", files.into())
+                    ]),
+                    description: None
+                }),
+
+                Err(err) => sender.input(AppMsg::Toast {
+                    title: err.title,
+                    description: err.description
+                })
+            }
+        });
     });
 }
@@ -0,0 +1,130 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+use relm4::prelude::*;
+
+use crate::*;
+use crate::i18n::*;
+
+use super::{App, AppMsg};
+
+/// How often running tasks are checked for timeouts and skip requests
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+pub const NETWORK_CHECK_TIMEOUT: Duration = Duration::from_secs(10);
+pub const BACKGROUND_TIMEOUT: Duration = Duration::from_secs(30);
+pub const COMPONENTS_TIMEOUT: Duration = Duration::from_secs(60);
+pub const GAME_DIFF_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Result of the game version startup task
+#[derive(Debug, Clone)]
+pub enum GameVersion {
+    Found(VersionDiff),
+
+    /// Task failed, timed out or was skipped
+    Unknown
+}
+
+/// Task which is run on the launcher start
+pub struct StartupTask {
+    /// Locale key of the task name
+    pub name: &'static str,
+
+    /// Task is abandoned if it's not finished in this time
+    pub timeout: Duration,
+
+    pub task: Box<dyn FnOnce() + Send>
+}
+
+impl StartupTask {
+    #[inline]
+    pub fn new(name: &'static str, timeout: Duration, task: impl FnOnce() + Send + 'static) -> Self {
+        Self {
+            name,
+            timeout,
+            task: Box::new(task)
+        }
+    }
+}
+
+/// Run startup tasks in parallel and wait until all of them are finished,
+/// timed out, or skipped by the user
+///
+/// Names of still running tasks are sent with `AppMsg::SetStartupTasks`.
+/// Abandoned tasks are not stopped, but their results are not awaited anymore
+pub fn run(sender: &ComponentSender<App>, tasks: Vec<StartupTask>, skip: Arc<AtomicBool>) {
+    let (finished_sender, finished) = mpsc::channel();
+
+    let started = Instant::now();
+
+    let mut running = tasks.into_iter()
+        .enumerate()
+        .map(|(i, StartupTask { name, timeout, task })| {
+            let finished_sender = finished_sender.clone();
+
+            std::thread::spawn(move || {
+                task();
+
+                #[allow(unused_must_use)] {
+                    finished_sender.send(i);
+                }
+            });
+
+            (i, name, timeout)
+        })
+        .collect::<Vec<_>>();
+
+    let update_list = |running: &[(usize, &'static str, Duration)]| {
+        sender.input(AppMsg::SetStartupTasks(running.iter()
+            .map(|(_, name, _)| tr(name))
+            .collect()));
+    };
+
+    update_list(&running);
+
+    while !running.is_empty() {
+        let mut changed = false;
+
+        // Remove finished tasks
+        if let Ok(i) = finished.recv_timeout(POLL_INTERVAL) {
+            running.retain(|(task, _, _)| *task != i);
+
+            changed = true;
+        }
+
+        // Abandon timed out tasks
+        running.retain(|(_, name, timeout)| {
+            if started.elapsed() < *timeout {
+                return true;
+            }
+
+            tracing::warn!("Startup task timed out: {name}");
+
+            sender.input(AppMsg::Toast {
+                title: tr_args("startup-task-timed-out", [
+                    ("task", tr(name).into())
+                ]),
+                description: None
+            });
+
+            changed = true;
+
+            false
+        });
+
+        // Abandon all the tasks if user doesn't want to wait for them
+        if skip.swap(false, Ordering::Relaxed) {
+            for (_, name, _) in running.drain(..) {
+                tracing::warn!("Startup task skipped: {name}");
+            }
+
+            changed = true;
+        }
+
+        if changed {
+            update_list(&running);
+        }
+    }
+}
@@ -527,7 +527,7 @@ impl SimpleAsyncComponent for EnhancementsApp {
                                 .expect("Failed to init Discord RPC icon downloader")
                                 .with_continue_downloading(false)
                                 .with_free_space_check(false)
-                                .download(cache_file, |_, _| {})
+                                .download(cache_file, crate::network::throttle_downloader(|_, _| {}))
                                 .expect("Failed to download Discord RPC icon");
 
                             /*if let Err(err) = result {
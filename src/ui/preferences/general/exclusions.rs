@@ -0,0 +1,196 @@
+use relm4::prelude::*;
+use relm4::component::*;
+use relm4::factory::*;
+
+use adw::prelude::*;
+
+use super::GeneralAppMsg;
+
+use crate::settings::Settings;
+use crate::i18n::*;
+
+#[derive(Debug)]
+struct Exclusion {
+    pattern: String
+}
+
+#[relm4::factory(async)]
+impl AsyncFactoryComponent for Exclusion {
+    type Init = String;
+    type Input = ();
+    type Output = ExclusionsPageMsg;
+    type CommandOutput = ();
+    type ParentInput = ExclusionsPageMsg;
+    type ParentWidget = adw::PreferencesGroup;
+
+    view! {
+        root = adw::ActionRow {
+            set_title: &self.pattern,
+
+            add_suffix = &gtk::Button {
+                set_icon_name: "user-trash-symbolic",
+                add_css_class: "flat",
+                set_valign: gtk::Align::Center,
+
+                connect_clicked[sender, index] => move |_| {
+                    sender.output(ExclusionsPageMsg::Remove(index.clone()));
+                }
+            }
+        }
+    }
+
+    async fn init_model(
+        init: Self::Init,
+        _index: &DynamicIndex,
+        _sender: AsyncFactorySender<Self>,
+    ) -> Self {
+        Self {
+            pattern: init
+        }
+    }
+
+    fn forward_to_parent(output: Self::Output) -> Option<Self::ParentInput> {
+        Some(output)
+    }
+}
+
+pub struct ExclusionsPage {
+    exclusions: AsyncFactoryVecDeque<Exclusion>,
+
+    pattern_entry: adw::EntryRow
+}
+
+#[derive(Debug, Clone)]
+pub enum ExclusionsPageMsg {
+    Add,
+    Remove(DynamicIndex)
+}
+
+#[relm4::component(async, pub)]
+impl SimpleAsyncComponent for ExclusionsPage {
+    type Init = ();
+    type Input = ExclusionsPageMsg;
+    type Output = GeneralAppMsg;
+
+    view! {
+        gtk::Box {
+            set_orientation: gtk::Orientation::Vertical,
+
+            adw::HeaderBar {
+                #[wrap(Some)]
+                set_title_widget = &adw::WindowTitle {
+                    set_title: &tr("repair-exclusions")
+                },
+
+                pack_start = &gtk::Button {
+                    set_icon_name: "go-previous-symbolic",
+
+                    connect_clicked[sender] => move |_| {
+                        sender.output(GeneralAppMsg::OpenMainPage).unwrap();
+                    }
+                }
+            },
+
+            adw::PreferencesPage {
+                set_title: &tr("repair-exclusions"),
+                set_icon_name: Some("action-unavailable-symbolic"),
+
+                add = &adw::PreferencesGroup {
+                    set_title: &tr("new-exclusion"),
+                    set_description: Some(&tr("new-exclusion-description")),
+
+                    #[wrap(Some)]
+                    set_header_suffix = &gtk::Button {
+                        add_css_class: "flat",
+
+                        set_valign: gtk::Align::Center,
+
+                        adw::ButtonContent {
+                            set_icon_name: "list-add-symbolic",
+                            set_label: &tr("add")
+                        },
+
+                        connect_clicked => ExclusionsPageMsg::Add
+                    },
+
+                    #[local_ref]
+                    pattern_entry -> adw::EntryRow {
+                        set_title: &tr("exclusion-pattern"),
+
+                        connect_entry_activated => ExclusionsPageMsg::Add
+                    }
+                },
+
+                add = &adw::PreferencesGroup {
+                    set_title: &tr("repair-exclusions"),
+                    set_description: Some(&tr("repair-exclusions-description"))
+                },
+
+                #[local_ref]
+                add = exclusions -> adw::PreferencesGroup {}
+            }
+        }
+    }
+
+    async fn init(
+        _init: Self::Init,
+        root: Self::Root,
+        sender: AsyncComponentSender<Self>,
+    ) -> AsyncComponentParts<Self> {
+        tracing::info!("Initializing repair exclusions settings");
+
+        let mut model = Self {
+            exclusions: AsyncFactoryVecDeque::new(adw::PreferencesGroup::new(), sender.input_sender()),
+
+            pattern_entry: adw::EntryRow::new()
+        };
+
+        if let Ok(settings) = Settings::get() {
+            for pattern in settings.repair.exclusions {
+                model.exclusions.guard().push_back(pattern);
+            }
+        }
+
+        let exclusions = model.exclusions.widget();
+
+        let pattern_entry = &model.pattern_entry;
+
+        let widgets = view_output!();
+
+        AsyncComponentParts { model, widgets }
+    }
+
+    async fn update(&mut self, msg: Self::Input, _sender: AsyncComponentSender<Self>) {
+        match msg {
+            ExclusionsPageMsg::Add => {
+                let pattern = self.pattern_entry.text().trim().to_string();
+
+                if !pattern.is_empty() {
+                    if let Ok(mut settings) = Settings::get() {
+                        if !settings.repair.exclusions.contains(&pattern) {
+                            self.pattern_entry.set_text("");
+
+                            settings.repair.exclusions.push(pattern.clone());
+
+                            Settings::update(settings);
+
+                            self.exclusions.guard().push_back(pattern);
+                        }
+                    }
+                }
+            }
+
+            ExclusionsPageMsg::Remove(index) => {
+                if let Ok(mut settings) = Settings::get() {
+                    if let Some(exclusion) = self.exclusions.guard().get(index.current_index()) {
+                        settings.repair.exclusions.retain(|pattern| pattern != &exclusion.pattern);
+
+                        Settings::update(settings);
+                    }
+
+                    self.exclusions.guard().remove(index.current_index());
+                }
+            }
+        }
+    }
+}
@@ -11,21 +11,27 @@ use anime_launcher_sdk::pgr::config::Config;
 use anime_launcher_sdk::pgr::config::schema::launcher::LauncherStyle;
 
 pub mod components;
+pub mod exclusions;
 
 use components::*;
+use exclusions::*;
 
 use super::main::PreferencesAppMsg;
 
 use crate::ui::migrate_installation::MigrateInstallationApp;
+use crate::settings::{Settings, AutoUpdate, RepairSettings};
 use crate::i18n::*;
 use crate::*;
 
 pub struct GeneralApp {
     migrate_installation: Controller<MigrateInstallationApp>,
     components_page: AsyncController<ComponentsPage>,
+    exclusions_page: AsyncController<ExclusionsPage>,
 
     game_diff: Option<VersionDiff>,
     style: LauncherStyle,
+    auto_update: AutoUpdate,
+    repair: RepairSettings,
 
     languages: Vec<String>
 }
@@ -41,9 +47,11 @@ pub enum GeneralAppMsg {
 
     OpenMigrateInstallation,
     RepairGame,
+    VerifyGame,
 
     OpenMainPage,
     OpenComponentsPage,
+    OpenExclusionsPage,
 
     UpdateLauncherStyle(LauncherStyle),
 
@@ -200,6 +208,13 @@ impl SimpleAsyncComponent for GeneralApp {
                         set_label: &tr("repair-game"),
 
                         connect_clicked => GeneralAppMsg::RepairGame
+                    },
+
+                    gtk::Button {
+                        set_label: &tr("verify-game"),
+                        set_tooltip_text: Some(&tr("verify-game-description")),
+
+                        connect_clicked => GeneralAppMsg::VerifyGame
                     }
                 }
             },
@@ -251,6 +266,135 @@ impl SimpleAsyncComponent for GeneralApp {
                 }
             },
 
+            add = &adw::PreferencesGroup {
+                set_title: &tr("auto-update"),
+                set_description: Some(&tr("auto-update-description")),
+
+                adw::ActionRow {
+                    set_title: &tr("auto-update"),
+
+                    add_suffix = &gtk::Switch {
+                        set_valign: gtk::Align::Center,
+                        set_active: model.auto_update.enabled,
+
+                        connect_state_notify => |switch| {
+                            if is_ready() {
+                                if let Ok(mut settings) = Settings::get() {
+                                    settings.auto_update.enabled = switch.state();
+
+                                    Settings::update(settings);
+                                }
+                            }
+                        }
+                    }
+                },
+
+                adw::EntryRow {
+                    set_title: &tr("auto-update-interval"),
+                    set_input_purpose: gtk::InputPurpose::Digits,
+
+                    set_text: &model.auto_update.interval.to_string(),
+
+                    connect_changed => |row| {
+                        if is_ready() {
+                            if let Ok(interval) = row.text().parse() {
+                                if let Ok(mut settings) = Settings::get() {
+                                    settings.auto_update.interval = interval;
+
+                                    Settings::update(settings);
+                                }
+                            }
+                        }
+                    }
+                },
+
+                adw::EntryRow {
+                    set_title: &tr("auto-update-at-hour"),
+                    set_tooltip_text: Some(&tr("auto-update-at-hour-description")),
+                    set_input_purpose: gtk::InputPurpose::Digits,
+
+                    set_text: &model.auto_update.at_hour
+                        .map(|hour| hour.to_string())
+                        .unwrap_or_default(),
+
+                    connect_changed => |row| {
+                        if is_ready() {
+                            let at_hour = match row.text().as_str() {
+                                "" => None,
+
+                                hour => match hour.parse() {
+                                    Ok(hour) if hour < 24 => Some(hour),
+                                    _ => return
+                                }
+                            };
+
+                            if let Ok(mut settings) = Settings::get() {
+                                settings.auto_update.at_hour = at_hour;
+
+                                Settings::update(settings);
+                            }
+                        }
+                    }
+                }
+            },
+
+            add = &adw::PreferencesGroup {
+                set_title: &tr("game-repair"),
+
+                adw::ActionRow {
+                    set_title: &tr("force-full-verify"),
+                    set_subtitle: &tr("force-full-verify-description"),
+
+                    add_suffix = &gtk::Switch {
+                        set_valign: gtk::Align::Center,
+                        set_active: model.repair.force_full_verify,
+
+                        connect_state_notify => |switch| {
+                            if is_ready() {
+                                if let Ok(mut settings) = Settings::get() {
+                                    settings.repair.force_full_verify = switch.state();
+
+                                    Settings::update(settings);
+                                }
+                            }
+                        }
+                    }
+                },
+
+                adw::ActionRow {
+                    set_title: &tr("verify-after-update"),
+                    set_subtitle: &tr("verify-after-update-description"),
+
+                    add_suffix = &gtk::Switch {
+                        set_valign: gtk::Align::Center,
+                        set_active: model.repair.verify_after_update,
+
+                        connect_state_notify => |switch| {
+                            if is_ready() {
+                                if let Ok(mut settings) = Settings::get() {
+                                    settings.repair.verify_after_update = switch.state();
+
+                                    Settings::update(settings);
+                                }
+                            }
+                        }
+                    }
+                },
+
+                adw::ActionRow {
+                    set_title: &tr("repair-exclusions"),
+                    set_subtitle: &tr("repair-exclusions-description"),
+
+                    add_suffix = &gtk::Image {
+                        set_icon_name: Some("go-next-symbolic")
+                    },
+
+                    set_activatable: true,
+
+                    connect_activated => GeneralAppMsg::OpenExclusionsPage
+                }
+            },
+
             add = &adw::PreferencesGroup {
                 set_title: &tr("options"),
 
@@ -328,7 +472,10 @@ impl SimpleAsyncComponent for GeneralApp {
         },
 
         #[local_ref]
-        components_page -> gtk::Box {}
+        components_page -> gtk::Box {},
+
+        #[local_ref]
+        exclusions_page -> gtk::Box {}
     }
 
     async fn init(
@@ -347,13 +494,20 @@ impl SimpleAsyncComponent for GeneralApp {
                 .launch(())
                 .forward(sender.input_sender(), std::convert::identity),
 
+            exclusions_page: ExclusionsPage::builder()
+                .launch(())
+                .forward(sender.input_sender(), std::convert::identity),
+
             game_diff: None,
             style: CONFIG.launcher.style,
+            auto_update: Settings::get().unwrap_or_default().auto_update,
+            repair: Settings::get().unwrap_or_default().repair,
 
             languages: SUPPORTED_LANGUAGES.iter().map(|lang| tr(format_lang(lang).as_str())).collect()
         };
 
         let components_page = model.components_page.widget();
+        let exclusions_page = model.exclusions_page.widget();
 
         let widgets = view_output!();
 
@@ -390,6 +544,10 @@ impl SimpleAsyncComponent for GeneralApp {
                 sender.output(Self::Output::RepairGame).unwrap();
             }
 
+            GeneralAppMsg::VerifyGame => {
+                sender.output(Self::Output::VerifyGame).unwrap();
+            }
+
             GeneralAppMsg::OpenMainPage => unsafe {
                 PREFERENCES_WINDOW.as_ref()
                     .unwrap_unchecked()
@@ -404,6 +562,13 @@ impl SimpleAsyncComponent for GeneralApp {
                     .present_subpage(self.components_page.widget());
             }
 
+            GeneralAppMsg::OpenExclusionsPage => unsafe {
+                PREFERENCES_WINDOW.as_ref()
+                    .unwrap_unchecked()
+                    .widget()
+                    .present_subpage(self.exclusions_page.widget());
+            }
+
             #[allow(unused_must_use)]
             GeneralAppMsg::UpdateLauncherStyle(style) => {
                 if style == LauncherStyle::Classic && !KEEP_BACKGROUND_FILE.exists() {
@@ -14,12 +14,14 @@ use crate::i18n::tr;
 
 use super::general::*;
 use super::enhancements::*;
+use super::network::*;
 
 pub static mut PREFERENCES_WINDOW: Option<adw::PreferencesWindow> = None;
 
 pub struct PreferencesApp {
     general: AsyncController<GeneralApp>,
-    enhancements: AsyncController<EnhancementsApp>
+    enhancements: AsyncController<EnhancementsApp>,
+    network: AsyncController<NetworkApp>
 }
 
 #[derive(Debug, Clone)]
@@ -32,6 +34,7 @@ pub enum PreferencesAppMsg {
 
     UpdateLauncherState,
     RepairGame,
+    VerifyGame,
 
     Toast {
         title: String,
@@ -56,6 +59,7 @@ impl SimpleAsyncComponent for PreferencesApp {
 
             add = model.general.widget(),
             add = model.enhancements.widget(),
+            add = model.network.widget(),
 
             connect_close_request[sender] => move |_| {
                 if let Err(err) = Config::flush() {
@@ -83,6 +87,10 @@ impl SimpleAsyncComponent for PreferencesApp {
                 .forward(sender.input_sender(), std::convert::identity),
 
             enhancements: EnhancementsApp::builder()
+                .launch(())
+                .forward(sender.input_sender(), std::convert::identity),
+
+            network: NetworkApp::builder()
                 .launch(())
                 .forward(sender.input_sender(), std::convert::identity)
         };
@@ -132,6 +140,13 @@ impl SimpleAsyncComponent for PreferencesApp {
                 sender.output(Self::Output::RepairGame);
             }
 
+            #[allow(unused_must_use)]
+            PreferencesAppMsg::VerifyGame => unsafe {
+                PREFERENCES_WINDOW.as_ref().unwrap_unchecked().close();
+
+                sender.output(Self::Output::VerifyGame);
+            }
+
             PreferencesAppMsg::Toast { title, description } => unsafe {
                 let toast = adw::Toast::new(&title);
 
@@ -1,4 +1,5 @@
 pub mod main;
 pub mod general;
 pub mod enhancements;
+pub mod network;
 pub mod gamescope;
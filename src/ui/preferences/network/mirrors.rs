@@ -0,0 +1,275 @@
+use relm4::prelude::*;
+use relm4::component::*;
+use relm4::factory::*;
+
+use adw::prelude::*;
+
+use super::NetworkAppMsg;
+
+use crate::settings::Settings;
+use crate::network::mirrors::latency;
+use crate::i18n::*;
+
+#[derive(Debug)]
+struct Mirror {
+    url: String,
+
+    /// Translated latency measurement result
+    latency: String
+}
+
+#[derive(Debug, Clone)]
+enum MirrorMsg {
+    SetLatency(String)
+}
+
+#[relm4::factory(async)]
+impl AsyncFactoryComponent for Mirror {
+    type Init = String;
+    type Input = MirrorMsg;
+    type Output = MirrorsPageMsg;
+    type CommandOutput = ();
+    type ParentInput = MirrorsPageMsg;
+    type ParentWidget = adw::PreferencesGroup;
+
+    view! {
+        root = adw::ActionRow {
+            set_title: &self.url,
+
+            #[watch]
+            set_subtitle: &self.latency,
+
+            add_suffix = &gtk::Button {
+                set_icon_name: "user-trash-symbolic",
+                add_css_class: "flat",
+                set_valign: gtk::Align::Center,
+
+                connect_clicked[sender, index] => move |_| {
+                    sender.output(MirrorsPageMsg::Remove(index.clone()));
+                }
+            }
+        }
+    }
+
+    async fn init_model(
+        init: Self::Init,
+        _index: &DynamicIndex,
+        _sender: AsyncFactorySender<Self>,
+    ) -> Self {
+        Self {
+            url: init,
+            latency: tr("latency-not-measured")
+        }
+    }
+
+    async fn update(&mut self, msg: Self::Input, _sender: AsyncFactorySender<Self>) {
+        match msg {
+            MirrorMsg::SetLatency(latency) => self.latency = latency
+        }
+    }
+
+    fn forward_to_parent(output: Self::Output) -> Option<Self::ParentInput> {
+        Some(output)
+    }
+}
+
+pub struct MirrorsPage {
+    mirrors: AsyncFactoryVecDeque<Mirror>,
+
+    url_entry: adw::EntryRow
+}
+
+#[derive(Debug, Clone)]
+pub enum MirrorsPageMsg {
+    Add,
+    Remove(DynamicIndex),
+
+    MeasureLatency,
+
+    LatencyMeasured {
+        url: String,
+        latency: String
+    }
+}
+
+#[relm4::component(async, pub)]
+impl SimpleAsyncComponent for MirrorsPage {
+    type Init = ();
+    type Input = MirrorsPageMsg;
+    type Output = NetworkAppMsg;
+
+    view! {
+        gtk::Box {
+            set_orientation: gtk::Orientation::Vertical,
+
+            adw::HeaderBar {
+                #[wrap(Some)]
+                set_title_widget = &adw::WindowTitle {
+                    set_title: &tr("mirrors")
+                },
+
+                pack_start = &gtk::Button {
+                    set_icon_name: "go-previous-symbolic",
+
+                    connect_clicked[sender] => move |_| {
+                        sender.output(NetworkAppMsg::OpenMainPage).unwrap();
+                    }
+                }
+            },
+
+            adw::PreferencesPage {
+                set_title: &tr("mirrors"),
+                set_icon_name: Some("network-server-symbolic"),
+
+                add = &adw::PreferencesGroup {
+                    set_title: &tr("new-mirror"),
+                    set_description: Some(&tr("new-mirror-description")),
+
+                    #[wrap(Some)]
+                    set_header_suffix = &gtk::Button {
+                        add_css_class: "flat",
+
+                        set_valign: gtk::Align::Center,
+
+                        adw::ButtonContent {
+                            set_icon_name: "list-add-symbolic",
+                            set_label: &tr("add")
+                        },
+
+                        connect_clicked => MirrorsPageMsg::Add
+                    },
+
+                    #[local_ref]
+                    url_entry -> adw::EntryRow {
+                        set_title: &tr("mirror-url"),
+                        set_input_purpose: gtk::InputPurpose::Url
+                    }
+                },
+
+                add = &adw::PreferencesGroup {
+                    set_title: &tr("mirrors"),
+                    set_description: Some(&tr("mirrors-description")),
+
+                    #[wrap(Some)]
+                    set_header_suffix = &gtk::Button {
+                        add_css_class: "flat",
+
+                        set_valign: gtk::Align::Center,
+
+                        adw::ButtonContent {
+                            set_icon_name: "view-refresh-symbolic",
+                            set_label: &tr("measure-latency")
+                        },
+
+                        connect_clicked => MirrorsPageMsg::MeasureLatency
+                    }
+                },
+
+                #[local_ref]
+                add = mirrors -> adw::PreferencesGroup {}
+            }
+        }
+    }
+
+    async fn init(
+        _init: Self::Init,
+        root: Self::Root,
+        sender: AsyncComponentSender<Self>,
+    ) -> AsyncComponentParts<Self> {
+        tracing::info!("Initializing mirrors settings");
+
+        let mut model = Self {
+            mirrors: AsyncFactoryVecDeque::new(adw::PreferencesGroup::new(), sender.input_sender()),
+
+            url_entry: adw::EntryRow::new()
+        };
+
+        if let Ok(settings) = Settings::get() {
+            for url in settings.network.mirrors {
+                model.mirrors.guard().push_back(url);
+            }
+        }
+
+        let mirrors = model.mirrors.widget();
+
+        let url_entry = &model.url_entry;
+
+        let widgets = view_output!();
+
+        AsyncComponentParts { model, widgets }
+    }
+
+    async fn update(&mut self, msg: Self::Input, sender: AsyncComponentSender<Self>) {
+        match msg {
+            MirrorsPageMsg::Add => {
+                let url = self.url_entry.text().trim().trim_end_matches('/').to_string();
+
+                if url.starts_with("http://") || url.starts_with("https://") {
+                    if let Ok(mut settings) = Settings::get() {
+                        if !settings.network.mirrors.contains(&url) {
+                            self.url_entry.set_text("");
+
+                            settings.network.mirrors.push(url.clone());
+
+                            Settings::update(settings);
+
+                            self.mirrors.guard().push_back(url);
+
+                            sender.input(MirrorsPageMsg::MeasureLatency);
+                        }
+                    }
+                }
+            }
+
+            MirrorsPageMsg::Remove(index) => {
+                if let Ok(mut settings) = Settings::get() {
+                    if let Some(mirror) = self.mirrors.guard().get(index.current_index()) {
+                        settings.network.mirrors.retain(|url| url != &mirror.url);
+
+                        Settings::update(settings);
+                    }
+
+                    self.mirrors.guard().remove(index.current_index());
+                }
+            }
+
+            MirrorsPageMsg::MeasureLatency => {
+                for i in 0..self.mirrors.len() {
+                    let Some(mirror) = self.mirrors.get(i) else {
+                        continue;
+                    };
+
+                    let url = mirror.url.clone();
+                    let sender = sender.clone();
+
+                    self.mirrors.send(i, MirrorMsg::SetLatency(tr("measuring-latency")));
+
+                    std::thread::spawn(move || {
+                        let latency = match latency(&url) {
+                            Ok(latency) => tr_args("mirror-latency", [
+                                ("ms", (latency.as_millis() as u64).into())
+                            ]),
+
+                            Err(err) => {
+                                tracing::warn!("Mirror {url} is unavailable: {err}");
+
+                                format!("{}: {err}", tr("mirror-unavailable"))
+                            }
+                        };
+
+                        sender.input(MirrorsPageMsg::LatencyMeasured { url, latency });
+                    });
+                }
+            }
+
+            MirrorsPageMsg::LatencyMeasured { url, latency } => {
+                let index = (0..self.mirrors.len())
+                    .find(|i| matches!(self.mirrors.get(*i), Some(mirror) if mirror.url == url));
+
+                if let Some(index) = index {
+                    self.mirrors.send(index, MirrorMsg::SetLatency(latency));
+                }
+            }
+        }
+    }
+}
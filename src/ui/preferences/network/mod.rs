@@ -0,0 +1,500 @@
+use std::path::PathBuf;
+
+use relm4::prelude::*;
+use relm4::component::*;
+
+use gtk::prelude::*;
+use adw::prelude::*;
+
+pub mod mirrors;
+
+use mirrors::*;
+
+use crate::settings::{Settings, ProxyKind};
+use crate::network::proxy;
+use crate::i18n::*;
+use crate::*;
+
+use super::main::PreferencesAppMsg;
+
+pub struct NetworkApp {
+    settings: Settings,
+
+    mirrors_page: AsyncController<MirrorsPage>
+}
+
+#[derive(Debug, Clone)]
+pub enum NetworkAppMsg {
+    SetSpeedLimitEnabled(bool),
+    SetSpeedLimit(u64),
+
+    SetScheduleEnabled(bool),
+    SetUnlimitedFrom(u8),
+    SetUnlimitedTo(u8),
+
+    SetRetries(u32),
+
+    SetProxyEnabled(bool),
+    SetProxyKind(ProxyKind),
+    SetProxyHost(String),
+    SetProxyPort(u16),
+    SetProxyUsername(String),
+    SetProxyPassword(String),
+    SetCaBundle(Option<PathBuf>),
+
+    ChooseCaBundle,
+    TestConnection,
+
+    OpenMainPage,
+    OpenMirrorsPage
+}
+
+#[relm4::component(async, pub)]
+impl SimpleAsyncComponent for NetworkApp {
+    type Init = ();
+    type Input = NetworkAppMsg;
+    type Output = PreferencesAppMsg;
+
+    view! {
+        #[root]
+        adw::PreferencesPage {
+            set_title: &tr("network"),
+            set_icon_name: Some("network-transmit-receive-symbolic"),
+
+            add = &adw::PreferencesGroup {
+                set_title: &tr("speed-limit"),
+                set_description: Some(&tr("speed-limit-description")),
+
+                adw::ActionRow {
+                    set_title: &tr("limit-download-speed"),
+
+                    add_suffix = &gtk::Switch {
+                        set_valign: gtk::Align::Center,
+                        set_active: model.settings.network.speed_limit.enabled,
+
+                        connect_state_notify[sender] => move |switch| {
+                            if is_ready() {
+                                sender.input(NetworkAppMsg::SetSpeedLimitEnabled(switch.state()));
+                            }
+                        }
+                    }
+                },
+
+                adw::EntryRow {
+                    set_title: &tr("speed-limit-kibs"),
+                    set_input_purpose: gtk::InputPurpose::Digits,
+
+                    #[watch]
+                    set_sensitive: model.settings.network.speed_limit.enabled,
+
+                    set_text: &model.settings.network.speed_limit.limit.to_string(),
+
+                    connect_changed[sender] => move |row| {
+                        if is_ready() {
+                            if let Ok(limit) = row.text().parse() {
+                                sender.input(NetworkAppMsg::SetSpeedLimit(limit));
+                            }
+                        }
+                    }
+                },
+
+                adw::ExpanderRow {
+                    set_title: &tr("speed-limit-schedule"),
+                    set_subtitle: &tr("speed-limit-schedule-description"),
+
+                    #[watch]
+                    set_sensitive: model.settings.network.speed_limit.enabled,
+
+                    set_show_enable_switch: true,
+                    set_enable_expansion: model.settings.network.speed_limit.schedule.enabled,
+
+                    connect_enable_expansion_notify[sender] => move |row| {
+                        if is_ready() {
+                            sender.input(NetworkAppMsg::SetScheduleEnabled(row.enables_expansion()));
+                        }
+                    },
+
+                    add_row = &adw::EntryRow {
+                        set_title: &tr("unlimited-from"),
+                        set_input_purpose: gtk::InputPurpose::Digits,
+
+                        set_text: &model.settings.network.speed_limit.schedule.unlimited_from.to_string(),
+
+                        connect_changed[sender] => move |row| {
+                            if is_ready() {
+                                match row.text().parse() {
+                                    Ok(hour) if hour < 24 => sender.input(NetworkAppMsg::SetUnlimitedFrom(hour)),
+                                    _ => ()
+                                }
+                            }
+                        }
+                    },
+
+                    add_row = &adw::EntryRow {
+                        set_title: &tr("unlimited-to"),
+                        set_input_purpose: gtk::InputPurpose::Digits,
+
+                        set_text: &model.settings.network.speed_limit.schedule.unlimited_to.to_string(),
+
+                        connect_changed[sender] => move |row| {
+                            if is_ready() {
+                                match row.text().parse() {
+                                    Ok(hour) if hour < 24 => sender.input(NetworkAppMsg::SetUnlimitedTo(hour)),
+                                    _ => ()
+                                }
+                            }
+                        }
+                    }
+                }
+            },
+
+            add = &adw::PreferencesGroup {
+                set_title: &tr("game-downloads"),
+
+                adw::ActionRow {
+                    set_title: &tr("mirrors"),
+                    set_subtitle: &tr("mirrors-settings-description"),
+
+                    add_suffix = &gtk::Image {
+                        set_icon_name: Some("go-next-symbolic")
+                    },
+
+                    set_activatable: true,
+
+                    connect_activated => NetworkAppMsg::OpenMirrorsPage
+                },
+
+                adw::EntryRow {
+                    set_title: &tr("download-retries"),
+                    set_input_purpose: gtk::InputPurpose::Digits,
+
+                    set_text: &model.settings.network.retries.to_string(),
+
+                    connect_changed[sender] => move |row| {
+                        if is_ready() {
+                            match row.text().parse() {
+                                Ok(retries) if retries > 0 => sender.input(NetworkAppMsg::SetRetries(retries)),
+                                _ => ()
+                            }
+                        }
+                    }
+                }
+            },
+
+            add = &adw::PreferencesGroup {
+                set_title: &tr("proxy"),
+                set_description: Some(&tr("proxy-description")),
+
+                #[wrap(Some)]
+                set_header_suffix = &gtk::Button {
+                    add_css_class: "flat",
+
+                    set_valign: gtk::Align::Center,
+
+                    adw::ButtonContent {
+                        set_icon_name: "network-transmit-receive-symbolic",
+                        set_label: &tr("test-connection")
+                    },
+
+                    connect_clicked => NetworkAppMsg::TestConnection
+                },
+
+                adw::ActionRow {
+                    set_title: &tr("proxy-restart-required"),
+
+                    add_prefix = &gtk::Image {
+                        set_icon_name: Some("dialog-warning-symbolic")
+                    },
+
+                    #[watch]
+                    set_visible: proxy::restart_required(&model.settings.network.proxy)
+                },
+
+                adw::ActionRow {
+                    set_title: &tr("use-proxy"),
+
+                    add_suffix = &gtk::Switch {
+                        set_valign: gtk::Align::Center,
+                        set_active: model.settings.network.proxy.enabled,
+
+                        connect_state_notify[sender] => move |switch| {
+                            if is_ready() {
+                                sender.input(NetworkAppMsg::SetProxyEnabled(switch.state()));
+                            }
+                        }
+                    }
+                },
+
+                adw::ComboRow {
+                    set_title: &tr("proxy-type"),
+
+                    #[watch]
+                    set_sensitive: model.settings.network.proxy.enabled,
+
+                    #[wrap(Some)]
+                    set_model = &gtk::StringList::new(&[
+                        "HTTP",
+                        "SOCKS5"
+                    ]),
+
+                    set_selected: match model.settings.network.proxy.kind {
+                        ProxyKind::Http   => 0,
+                        ProxyKind::Socks5 => 1
+                    },
+
+                    connect_selected_notify[sender] => move |row| {
+                        if is_ready() {
+                            sender.input(NetworkAppMsg::SetProxyKind(match row.selected() {
+                                1 => ProxyKind::Socks5,
+                                _ => ProxyKind::Http
+                            }));
+                        }
+                    }
+                },
+
+                adw::EntryRow {
+                    set_title: &tr("proxy-host"),
+
+                    #[watch]
+                    set_sensitive: model.settings.network.proxy.enabled,
+
+                    set_text: &model.settings.network.proxy.host,
+
+                    connect_changed[sender] => move |row| {
+                        if is_ready() {
+                            sender.input(NetworkAppMsg::SetProxyHost(row.text().trim().to_string()));
+                        }
+                    }
+                },
+
+                adw::EntryRow {
+                    set_title: &tr("proxy-port"),
+                    set_input_purpose: gtk::InputPurpose::Digits,
+
+                    #[watch]
+                    set_sensitive: model.settings.network.proxy.enabled,
+
+                    set_text: &model.settings.network.proxy.port.to_string(),
+
+                    connect_changed[sender] => move |row| {
+                        if is_ready() {
+                            if let Ok(port) = row.text().parse() {
+                                sender.input(NetworkAppMsg::SetProxyPort(port));
+                            }
+                        }
+                    }
+                },
+
+                adw::EntryRow {
+                    set_title: &tr("proxy-username"),
+
+                    #[watch]
+                    set_sensitive: model.settings.network.proxy.enabled,
+
+                    set_text: &model.settings.network.proxy.username,
+
+                    connect_changed[sender] => move |row| {
+                        if is_ready() {
+                            sender.input(NetworkAppMsg::SetProxyUsername(row.text().trim().to_string()));
+                        }
+                    }
+                },
+
+                adw::PasswordEntryRow {
+                    set_title: &tr("proxy-password"),
+
+                    #[watch]
+                    set_sensitive: model.settings.network.proxy.enabled,
+
+                    set_text: &model.settings.network.proxy.password,
+
+                    connect_changed[sender] => move |row| {
+                        if is_ready() {
+                            sender.input(NetworkAppMsg::SetProxyPassword(row.text().to_string()));
+                        }
+                    }
+                },
+
+                adw::ActionRow {
+                    set_title: &tr("ca-bundle"),
+
+                    #[watch]
+                    set_sensitive: model.settings.network.proxy.enabled,
+
+                    #[watch]
+                    set_subtitle: &match &model.settings.network.proxy.ca_bundle {
+                        Some(path) => path.to_string_lossy().to_string(),
+                        None => tr("ca-bundle-system")
+                    },
+
+                    add_suffix = &gtk::Button {
+                        set_icon_name: "edit-clear-symbolic",
+                        add_css_class: "flat",
+                        set_valign: gtk::Align::Center,
+
+                        #[watch]
+                        set_visible: model.settings.network.proxy.ca_bundle.is_some(),
+
+                        connect_clicked => NetworkAppMsg::SetCaBundle(None)
+                    },
+
+                    add_suffix = &gtk::Button {
+                        set_icon_name: "document-open-symbolic",
+                        add_css_class: "flat",
+                        set_valign: gtk::Align::Center,
+
+                        connect_clicked => NetworkAppMsg::ChooseCaBundle
+                    }
+                }
+            }
+        },
+
+        #[local_ref]
+        mirrors_page -> gtk::Box {}
+    }
+
+    async fn init(
+        _init: Self::Init,
+        root: Self::Root,
+        sender: AsyncComponentSender<Self>,
+    ) -> AsyncComponentParts<Self> {
+        tracing::info!("Initializing network settings");
+
+        let settings = match Settings::get() {
+            Ok(settings) => settings,
+            Err(err) => {
+                tracing::error!("Failed to read launcher settings: {err}");
+
+                #[allow(unused_must_use)] {
+                    sender.output(PreferencesAppMsg::Toast {
+                        title: tr("settings-reading-error"),
+                        description: Some(err.to_string())
+                    });
+                }
+
+                Settings::default()
+            }
+        };
+
+        let model = Self {
+            settings,
+
+            mirrors_page: MirrorsPage::builder()
+                .launch(())
+                .forward(sender.input_sender(), std::convert::identity)
+        };
+
+        let mirrors_page = model.mirrors_page.widget();
+
+        let widgets = view_output!();
+
+        AsyncComponentParts { model, widgets }
+    }
+
+    async fn update(&mut self, msg: Self::Input, sender: AsyncComponentSender<Self>) {
+        match msg {
+            NetworkAppMsg::OpenMainPage => unsafe {
+                PREFERENCES_WINDOW.as_ref()
+                    .unwrap_unchecked()
+                    .widget()
+                    .close_subpage();
+
+                return;
+            }
+
+            NetworkAppMsg::OpenMirrorsPage => {
+                self.mirrors_page.emit(MirrorsPageMsg::MeasureLatency);
+
+                unsafe {
+                    PREFERENCES_WINDOW.as_ref()
+                        .unwrap_unchecked()
+                        .widget()
+                        .present_subpage(self.mirrors_page.widget());
+                }
+
+                return;
+            }
+
+            NetworkAppMsg::ChooseCaBundle => {
+                let result = rfd::AsyncFileDialog::new()
+                    .add_filter("PEM", &["pem", "crt"])
+                    .pick_file().await;
+
+                if let Some(result) = result {
+                    sender.input(NetworkAppMsg::SetCaBundle(Some(result.path().to_path_buf())));
+                }
+
+                return;
+            }
+
+            NetworkAppMsg::TestConnection => {
+                // Edited settings are tested, even if they're not applied yet
+                let proxy_settings = self.settings.network.proxy.clone();
+
+                std::thread::spawn(move || {
+                    let toast = match proxy::test_connection(&proxy_settings) {
+                        Ok(time) => PreferencesAppMsg::Toast {
+                            title: tr_args("connection-succeeded", [
+                                ("ms", (time.as_millis() as u64).into())
+                            ]),
+                            description: None
+                        },
+
+                        Err(err) => {
+                            tracing::error!("Connection test failed: {err}");
+
+                            PreferencesAppMsg::Toast {
+                                title: tr("connection-failed"),
+                                description: Some(err.to_string())
+                            }
+                        }
+                    };
+
+                    #[allow(unused_must_use)] {
+                        sender.output(toast);
+                    }
+                });
+
+                return;
+            }
+
+            _ => ()
+        }
+
+        let apply = |settings: &mut Settings| {
+            let speed_limit = &mut settings.network.speed_limit;
+
+            match msg.clone() {
+                NetworkAppMsg::SetSpeedLimitEnabled(enabled) => speed_limit.enabled = enabled,
+                NetworkAppMsg::SetSpeedLimit(limit) => speed_limit.limit = limit,
+
+                NetworkAppMsg::SetScheduleEnabled(enabled) => speed_limit.schedule.enabled = enabled,
+                NetworkAppMsg::SetUnlimitedFrom(hour) => speed_limit.schedule.unlimited_from = hour,
+                NetworkAppMsg::SetUnlimitedTo(hour) => speed_limit.schedule.unlimited_to = hour,
+
+                NetworkAppMsg::SetRetries(retries) => settings.network.retries = retries,
+
+                NetworkAppMsg::SetProxyEnabled(enabled) => settings.network.proxy.enabled = enabled,
+                NetworkAppMsg::SetProxyKind(kind) => settings.network.proxy.kind = kind,
+                NetworkAppMsg::SetProxyHost(host) => settings.network.proxy.host = host,
+                NetworkAppMsg::SetProxyPort(port) => settings.network.proxy.port = port,
+                NetworkAppMsg::SetProxyUsername(username) => settings.network.proxy.username = username,
+                NetworkAppMsg::SetProxyPassword(password) => settings.network.proxy.password = password,
+                NetworkAppMsg::SetCaBundle(path) => settings.network.proxy.ca_bundle = path,
+
+                NetworkAppMsg::ChooseCaBundle |
+                NetworkAppMsg::TestConnection |
+                NetworkAppMsg::OpenMainPage |
+                NetworkAppMsg::OpenMirrorsPage => ()
+            }
+        };
+
+        apply(&mut self.settings);
+
+        if let Ok(mut settings) = Settings::get() {
+            apply(&mut settings);
+
+            Settings::update(settings);
+        }
+    }
+}